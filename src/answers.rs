@@ -0,0 +1,253 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use miette::{bail, miette, IntoDiagnostic, Result, WrapErr};
+
+use crate::util::{input_dir, Answer, Part};
+
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+/// The expected answers of the puzzle inputs, read from a TOML file with one table per day and
+/// input. Inputs are identified by the [`fingerprint`] of their content, so every teammate's
+/// input has its own answers:
+///
+/// ```toml
+/// [day17.5b8e1f0c9a2d4e67]
+/// part1 = "7,3,0,5,7,1,4,0,5"
+/// part2 = 202972175280682
+/// ```
+#[derive(Debug, Default)]
+pub struct Answers {
+    expected: HashMap<(u8, String, Part), String>,
+}
+
+impl Answers {
+    /// `answers.toml` next to the puzzle inputs.
+    pub fn default_path() -> PathBuf {
+        input_dir().join(ANSWERS_FILE)
+    }
+
+    /// Loads the answers, a missing file counts as a manifest without answers.
+    pub fn load(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(s) => Answers::parse(&s).wrap_err_with(|| format!("Invalid {}", path.display())),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(miette!("Failed to read {}: {}", path.display(), e)),
+        }
+    }
+
+    pub fn parse(s: &str) -> Result<Self> {
+        let table = s.parse::<toml::Table>().into_diagnostic()?;
+
+        let mut expected = HashMap::new();
+        for (key, value) in table {
+            let day = key
+                .strip_prefix("day")
+                .and_then(|n| n.parse::<u8>().ok())
+                .ok_or_else(|| miette!("Unexpected table [{}], expected [dayNN].", key))?;
+            let toml::Value::Table(inputs) = value else {
+                bail!("Expected [{}] to be a table.", key);
+            };
+
+            for (fingerprint, parts) in inputs {
+                let toml::Value::Table(parts) = parts else {
+                    bail!(
+                        "Expected [{}.{}] to be a table, answers are keyed by input fingerprint.",
+                        key,
+                        fingerprint
+                    );
+                };
+
+                for (part_key, answer) in parts {
+                    let part = match part_key.as_str() {
+                        "part1" => Part::One,
+                        "part2" => Part::Two,
+                        _ => bail!("Unexpected key {} in [{}.{}].", part_key, key, fingerprint),
+                    };
+                    let answer = match answer {
+                        toml::Value::String(s) => s,
+                        toml::Value::Integer(n) => n.to_string(),
+                        _ => bail!(
+                            "Expected {}.{}.{} to be a string or an integer.",
+                            key,
+                            fingerprint,
+                            part_key
+                        ),
+                    };
+                    expected.insert((day, fingerprint.clone(), part), answer);
+                }
+            }
+        }
+
+        Ok(Answers { expected })
+    }
+
+    /// The expected answer of a part for the input with the given fingerprint.
+    pub fn get(&self, day: u8, fingerprint: &str, part: Part) -> Option<&str> {
+        self.expected
+            .get(&(day, fingerprint.to_string(), part))
+            .map(|s| s.as_str())
+    }
+}
+
+/// Identifies a puzzle input by its content: the FNV-1a hash of the input as 16 hex digits.
+/// Line endings and trailing whitespace are ignored, so a checkout with CRLF line endings still
+/// finds its answers.
+pub fn fingerprint(input: &str) -> String {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    let mut hash = OFFSET_BASIS;
+    for line in input.trim_end().lines() {
+        for byte in line.trim_end().bytes().chain([b'\n']) {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(PRIME);
+        }
+    }
+    format!("{:016x}", hash)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail {
+        expected: String,
+        actual: String,
+    },
+    /// there is no expected answer for a solved part
+    Missing(String),
+    Unsolved,
+}
+
+impl Verdict {
+    pub fn new(expected: Option<&str>, answer: &Answer) -> Self {
+        match (expected, answer) {
+            (_, Answer::Unsolved) => Verdict::Unsolved,
+            (None, answer) => Verdict::Missing(answer.to_string()),
+            (Some(expected), answer) if answer == &expected => Verdict::Pass,
+            (Some(expected), answer) => Verdict::Fail {
+                expected: expected.to_string(),
+                actual: answer.to_string(),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "pass"),
+            Verdict::Fail { expected, actual } => {
+                write!(f, "FAIL (expected {}, got {})", expected, actual)
+            }
+            Verdict::Missing(actual) => write!(f, "missing (got {})", actual),
+            Verdict::Unsolved => write!(f, "unsolved"),
+        }
+    }
+}
+
+/// Checks the answer of a part for every puzzle input of the day against the answers manifest.
+/// Inputs without an expected answer are skipped, so the tests pass with anybody's input.
+#[cfg(test)]
+pub fn assert_input_answer(day: &crate::util::Day, part: Part) {
+    let answers = Answers::load(&Answers::default_path()).unwrap();
+    for input in day.inputs().unwrap() {
+        let source = crate::util::InputSource::File(input.path.clone());
+        let content = day.read_input_from(&source).unwrap();
+        let Some(expected) = answers.get(day.number(), &fingerprint(&content), part) else {
+            eprintln!(
+                "Day {} {} ({} input): no expected answer, skipped.",
+                day.number(),
+                part,
+                input
+            );
+            continue;
+        };
+
+        assert_eq!(
+            day.solve_part(part, &content).unwrap(),
+            expected,
+            "{} input",
+            input
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let answers = Answers::parse(
+            r#"
+[day01.0123456789abcdef]
+part1 = 11
+part2 = "31"
+
+[day01.fedcba9876543210]
+part1 = 12
+
+[day17.0123456789abcdef]
+part1 = "4,6,3,5,6,3,5,2,1,0"
+"#,
+        )
+        .unwrap();
+
+        let alice = "0123456789abcdef";
+        let bob = "fedcba9876543210";
+        assert_eq!(answers.get(1, alice, Part::One), Some("11"));
+        assert_eq!(answers.get(1, alice, Part::Two), Some("31"));
+        assert_eq!(answers.get(1, bob, Part::One), Some("12"));
+        assert_eq!(answers.get(1, bob, Part::Two), None);
+        assert_eq!(
+            answers.get(17, alice, Part::One),
+            Some("4,6,3,5,6,3,5,2,1,0")
+        );
+        assert_eq!(answers.get(17, bob, Part::One), None);
+        assert_eq!(answers.get(2, alice, Part::One), None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Answers::parse("[first.abc]\npart1 = 1").is_err());
+        assert!(Answers::parse("[day01.abc]\npart3 = 1").is_err());
+        assert!(Answers::parse("[day01.abc]\npart1 = 1.5").is_err());
+        assert!(Answers::parse("[day01]\npart1 = 1").is_err());
+        assert!(Answers::parse("day01 = 1").is_err());
+    }
+
+    #[test]
+    fn test_fingerprint() {
+        assert_eq!(fingerprint(""), "cbf29ce484222325");
+        assert_eq!(fingerprint("3   4\n4   3\n"), fingerprint("3   4\r\n4   3"));
+        assert_eq!(
+            fingerprint("3   4 \n4   3\n\n"),
+            fingerprint("3   4\n4   3")
+        );
+        assert_ne!(fingerprint("3   4\n4   3"), fingerprint("4   3\n3   4"));
+    }
+
+    #[test]
+    fn test_verdict() {
+        assert_eq!(Verdict::new(Some("11"), &Answer::Int(11)), Verdict::Pass);
+        assert_eq!(
+            Verdict::new(Some("11"), &Answer::Int(12)),
+            Verdict::Fail {
+                expected: "11".to_string(),
+                actual: "12".to_string()
+            }
+        );
+        assert_eq!(
+            Verdict::new(None, &Answer::from("6,1")),
+            Verdict::Missing("6,1".to_string())
+        );
+        assert_eq!(
+            Verdict::new(Some("11"), &Answer::Unsolved),
+            Verdict::Unsolved
+        );
+    }
+}
@@ -0,0 +1,195 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::util::Part;
+
+pub const TIMINGS_PATH: &str = "timings.md";
+
+// the part column of the rows that time the parsing of the input
+const PARSE: &str = "parse";
+
+const TABLE_HEADER: &str = "# Timings
+
+Generated with `cargo run --release -- bench --save`. All durations are wall-clock times of a single run.
+
+| Day | Title | Part | Runs | Min | Median | p95 |
+|----:|-------|:----:|-----:|----:|-------:|----:|
+";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        assert!(!samples.is_empty());
+        samples.sort();
+
+        Stats {
+            runs: samples.len(),
+            min: samples[0],
+            median: percentile(&samples, 50),
+            p95: percentile(&samples, 95),
+        }
+    }
+}
+
+// nearest-rank percentile of sorted samples
+fn percentile(samples: &[Duration], p: usize) -> Duration {
+    let rank = (p * samples.len()).div_ceil(100).max(1);
+    samples[rank - 1]
+}
+
+pub fn measure<F: FnMut()>(mut f: F, warmup: u32, iterations: u32) -> Stats {
+    measure_with(|| (), |_| f(), warmup, iterations)
+}
+
+/// Measures `f` on a fresh value of `setup` in every run, only `f` is timed.
+pub fn measure_with<S, F: FnMut(&mut S)>(
+    mut setup: impl FnMut() -> S,
+    mut f: F,
+    warmup: u32,
+    iterations: u32,
+) -> Stats {
+    for _ in 0..warmup {
+        f(&mut setup());
+    }
+
+    let samples = (0..iterations.max(1))
+        .map(|_| {
+            let mut value = setup();
+            let start = Instant::now();
+            f(&mut value);
+            start.elapsed()
+        })
+        .collect();
+
+    Stats::from_samples(samples)
+}
+
+#[derive(Debug)]
+pub struct Timing {
+    pub day: u8,
+    pub title: String,
+    /// the part that was solved, None for parsing the input
+    pub part: Option<Part>,
+    pub stats: Stats,
+}
+
+impl Timing {
+    fn row(&self) -> String {
+        format!(
+            "| {} | {} | {} | {} | {:.2?} | {:.2?} | {:.2?} |",
+            self.day,
+            self.title,
+            match self.part {
+                Some(part) => part_number(part).to_string(),
+                None => PARSE.to_string(),
+            },
+            self.stats.runs,
+            self.stats.min,
+            self.stats.median,
+            self.stats.p95
+        )
+    }
+
+    // the parse row sorts before the parts of its day
+    fn key(&self) -> (u8, u8) {
+        (self.day, self.part.map_or(0, part_number))
+    }
+}
+
+fn part_number(part: Part) -> u8 {
+    match part {
+        Part::One => 1,
+        Part::Two => 2,
+    }
+}
+
+// Returns day and part of a table row, None for all other lines.
+fn row_key(line: &str) -> Option<(u8, u8)> {
+    let mut cells = line.strip_prefix('|')?.split('|').map(|cell| cell.trim());
+    let day = cells.next()?.parse().ok()?;
+    let _title = cells.next()?;
+    let part = match cells.next()? {
+        PARSE => 0,
+        part => part.parse().ok()?,
+    };
+    Some((day, part))
+}
+
+/// Writes the timings to a markdown table. Rows of days and parts that were not measured this
+/// time are kept from an existing table, so benchmarking a single day only updates its rows.
+pub fn write_table(path: &Path, timings: &[Timing]) -> io::Result<()> {
+    let mut rows: Vec<((u8, u8), String)> = timings
+        .iter()
+        .map(|timing| (timing.key(), timing.row()))
+        .collect();
+
+    if let Ok(existing) = fs::read_to_string(path) {
+        for line in existing.lines() {
+            if let Some(key) = row_key(line) {
+                if !rows.iter().any(|(k, _)| *k == key) {
+                    rows.push((key, line.to_string()));
+                }
+            }
+        }
+    }
+    rows.sort_by_key(|(key, _)| *key);
+
+    let mut table = TABLE_HEADER.to_string();
+    for (_, row) in rows {
+        table.push_str(&row);
+        table.push('\n');
+    }
+
+    fs::write(path, table)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = Stats::from_samples((1..=20).rev().map(ms).collect());
+        assert_eq!(stats.runs, 20);
+        assert_eq!(stats.min, ms(1));
+        assert_eq!(stats.median, ms(10));
+        assert_eq!(stats.p95, ms(19));
+
+        let stats = Stats::from_samples(vec![ms(7)]);
+        assert_eq!((stats.min, stats.median, stats.p95), (ms(7), ms(7), ms(7)));
+    }
+
+    #[test]
+    fn test_row_key() {
+        assert_eq!(
+            row_key("| 17 | Chronospatial Computer | 2 | 10 | 1ms | 1ms | 1ms |"),
+            Some((17, 2))
+        );
+        assert_eq!(
+            row_key("| 16 | Reindeer Maze | parse | 10 | 1ms | 1ms | 1ms |"),
+            Some((16, 0))
+        );
+        assert_eq!(
+            row_key("| Day | Title | Part | Runs | Min | Median | p95 |"),
+            None
+        );
+        assert_eq!(
+            row_key("|----:|-------|:----:|-----:|----:|-------:|----:|"),
+            None
+        );
+        assert_eq!(row_key("# Timings"), None);
+    }
+}
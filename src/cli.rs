@@ -0,0 +1,868 @@
+use std::fs::{self, File};
+use std::io::{self, BufReader, IsTerminal};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::str::FromStr;
+use std::time::Duration;
+
+use clap::{ArgAction, Parser, Subcommand};
+
+use crate::answers::{self, Answers, Verdict};
+use crate::bench::{self, Stats, Timing};
+use crate::day17::{self, asm, debugger};
+use crate::days;
+use crate::logger;
+use crate::scaffold;
+use crate::util::{export, record, render};
+use crate::util::{print_error, with_input, Day, DayResult, InputSource, Params, Part};
+
+#[derive(Debug, Parser)]
+#[command(about = "Advent of Code 2024 solutions", version)]
+pub struct Cli {
+    #[command(subcommand)]
+    command: Command,
+
+    /// Log what the solvers do on stderr, -vv for all details
+    #[arg(short, long, action = ArgAction::Count, global = true)]
+    verbose: u8,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Solve a day, a range of days (e.g. 3-7) or all days
+    Run {
+        /// Day number, range of days or "all"
+        days: DaySelection,
+
+        /// Only solve the given part
+        #[arg(long)]
+        part: Option<Part>,
+
+        /// Read the input from this file instead of the input directory, "-" reads stdin
+        #[arg(long, value_name = "PATH")]
+        input: Option<String>,
+
+        /// Solve an example of the day instead, selected by number or name
+        #[arg(long, value_name = "N", conflicts_with = "input")]
+        example: Option<String>,
+
+        /// Override a parameter of the day, e.g. --param blinks=500 (repeatable)
+        #[arg(long = "param", value_name = "NAME=VALUE", value_parser = parse_param)]
+        params: Vec<(String, i64)>,
+
+        /// Draw the grids of the puzzles in the terminal, coloured if stdout is a terminal
+        #[arg(long)]
+        render: bool,
+
+        /// Record the steps of the simulations as asciinema cast to replay them later
+        #[arg(long, value_name = "PATH")]
+        record: Option<PathBuf>,
+
+        /// Frames per second of the recording
+        #[arg(long, default_value_t = 10, requires = "record")]
+        fps: u32,
+
+        /// Only record every n-th step of the simulations
+        #[arg(long, value_name = "N", default_value_t = 1, requires = "record")]
+        skip: u32,
+
+        /// Export the grids of the puzzles as images, the format is taken from the extension
+        /// (.ppm, .png or .svg) and the day and name of each image are added to the file name
+        #[arg(long, value_name = "PATH")]
+        export: Option<PathBuf>,
+    },
+
+    /// List the implemented and the missing days
+    List,
+
+    /// Measure how long the solvers take
+    Bench {
+        /// Day number, range of days or "all"
+        #[arg(default_value = "all")]
+        days: DaySelection,
+
+        /// Only measure the given part
+        #[arg(long)]
+        part: Option<Part>,
+
+        /// Read the input from this file instead of the input directory, "-" reads stdin
+        #[arg(long, value_name = "PATH")]
+        input: Option<String>,
+
+        /// Override a parameter of the day, e.g. --param blinks=500 (repeatable)
+        #[arg(long = "param", value_name = "NAME=VALUE", value_parser = parse_param)]
+        params: Vec<(String, i64)>,
+
+        /// Number of measured runs per part
+        #[arg(long, default_value_t = 10)]
+        iterations: u32,
+
+        /// Number of unmeasured runs per part before the measurement starts
+        #[arg(long, default_value_t = 2)]
+        warmup: u32,
+
+        /// Write the timings to a markdown table, updating the rows of the measured days
+        #[arg(long, value_name = "PATH", num_args = 0..=1, default_missing_value = bench::TIMINGS_PATH)]
+        save: Option<PathBuf>,
+    },
+
+    /// Compare the answers for every input of the selected days with the answers manifest
+    Verify {
+        /// Day number, range of days or "all"
+        #[arg(default_value = "all")]
+        days: DaySelection,
+
+        /// Answers manifest [default: answers.toml in the input directory]
+        #[arg(long, value_name = "PATH")]
+        answers: Option<PathBuf>,
+    },
+
+    /// Tools for the programs of day 17
+    Day17 {
+        #[command(subcommand)]
+        command: Day17Command,
+    },
+
+    /// Create and register the module of a new day from the day template
+    New {
+        /// Day number
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// Title of the puzzle
+        #[arg(long, default_value = "TBD")]
+        title: String,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum Day17Command {
+    /// Step through the program with a debugger, the commands are read from stdin or a script
+    /// file
+    Debug {
+        /// Read the input from this file instead of the input directory
+        #[arg(long, value_name = "PATH")]
+        input: Option<PathBuf>,
+
+        /// Debug an example of day 17 instead, selected by number or name
+        #[arg(long, value_name = "N", conflicts_with = "input")]
+        example: Option<String>,
+
+        /// Read the debugger commands from this file instead of stdin
+        #[arg(long, value_name = "PATH")]
+        script: Option<PathBuf>,
+
+        /// Write the trace of the executed instructions to this file, as JSON if it ends
+        /// with .json
+        #[arg(long, value_name = "PATH")]
+        trace: Option<PathBuf>,
+    },
+
+    /// Print the program of the input as assembly
+    Disasm {
+        /// Read the input from this file instead of the input directory
+        #[arg(long, value_name = "PATH")]
+        input: Option<PathBuf>,
+
+        /// Disassemble an example of day 17 instead, selected by number or name
+        #[arg(long, value_name = "N", conflicts_with = "input")]
+        example: Option<String>,
+    },
+
+    /// Assemble a program into the format of the puzzle input
+    Asm {
+        /// Assembly source, e.g. the output of disasm
+        source: PathBuf,
+
+        /// Write the puzzle input to this file instead of stdout
+        #[arg(long, short, value_name = "PATH")]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Clone, Copy, Debug)]
+enum DaySelection {
+    All,
+    Range(u8, u8),
+}
+
+impl DaySelection {
+    fn days(&self) -> Result<Vec<Day>, String> {
+        let days = match *self {
+            DaySelection::All => days::all(),
+            DaySelection::Range(first, last) if first == last => match days::get(first) {
+                Some(day) => vec![day],
+                None => return Err(format!("Day {} is not implemented.", first)),
+            },
+            DaySelection::Range(first, last) => days::in_range(first, last),
+        };
+
+        if days.is_empty() {
+            return Err("No implemented day in the selection.".to_string());
+        }
+        Ok(days)
+    }
+}
+
+impl FromStr for DaySelection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "all" {
+            return Ok(DaySelection::All);
+        }
+
+        let parse_day = |day: &str| match day.trim().parse::<u8>() {
+            Ok(n) if (days::FIRST_DAY..=days::LAST_DAY).contains(&n) => Ok(n),
+            _ => Err(format!(
+                "invalid day '{}', expected a number between {} and {}",
+                day,
+                days::FIRST_DAY,
+                days::LAST_DAY
+            )),
+        };
+
+        match s.split_once('-') {
+            Some((first, last)) => {
+                let (first, last) = (parse_day(first)?, parse_day(last)?);
+                if first > last {
+                    return Err(format!(
+                        "invalid range '{}', {} is after {}",
+                        s, first, last
+                    ));
+                }
+                Ok(DaySelection::Range(first, last))
+            }
+            None => parse_day(s).map(|day| DaySelection::Range(day, day)),
+        }
+    }
+}
+
+// An explicit input only makes sense for a single day.
+fn input_source(input: Option<&str>, days: &[Day]) -> Result<InputSource, String> {
+    match input {
+        None => Ok(InputSource::Default),
+        Some(_) if days.len() > 1 => Err("--input can only be used with a single day.".to_string()),
+        Some(arg) => Ok(InputSource::from_arg(arg)),
+    }
+}
+
+fn example_source(example: String, days: &[Day]) -> Result<InputSource, String> {
+    match days.len() {
+        1 => Ok(InputSource::Example(example)),
+        _ => Err("--example can only be used with a single day.".to_string()),
+    }
+}
+
+fn parse_param(s: &str) -> Result<(String, i64), String> {
+    let (name, value) = s
+        .split_once('=')
+        .ok_or_else(|| format!("invalid parameter '{}', expected NAME=VALUE", s))?;
+    let value = value
+        .trim()
+        .replace('_', "")
+        .parse::<i64>()
+        .map_err(|_| format!("invalid value '{}' of parameter {}", value, name))?;
+    Ok((name.trim().to_string(), value))
+}
+
+fn param_values(params: &[(String, i64)]) -> Params {
+    let mut values = Params::default();
+    for (name, value) in params {
+        values.set(name, *value);
+    }
+    values
+}
+
+fn selected_parts(part: Option<Part>) -> Vec<Part> {
+    match part {
+        Some(part) => vec![part],
+        None => Part::BOTH.to_vec(),
+    }
+}
+
+pub fn run() -> ExitCode {
+    let cli = Cli::parse();
+    logger::init(cli.verbose);
+
+    let result = match cli.command {
+        Command::Run {
+            days,
+            part,
+            input,
+            example,
+            params,
+            render,
+            record,
+            fps,
+            skip,
+            export,
+        } => days.days().and_then(|days| {
+            if render {
+                render::enable(render::colour_supported());
+            }
+            let source = match example {
+                Some(example) => example_source(example, &days)?,
+                None => input_source(input.as_deref(), &days)?,
+            };
+            if let Some(path) = &record {
+                record::start(path, record::Options { fps, skip }).map_err(|e| e.to_string())?;
+            }
+            if let Some(path) = &export {
+                export::start(path).map_err(|e| e.to_string())?;
+            }
+            let result = run_days(&days, part, &source, &param_values(&params));
+            match record::finish() {
+                Ok(Some((path, frames))) => {
+                    println!("Recorded {} frames to {}.", frames, path.display())
+                }
+                Ok(None) => (),
+                Err(err) => print_error(&err),
+            }
+            match export::finish() {
+                Ok(paths) => {
+                    for path in paths {
+                        println!("Exported {}.", path.display());
+                    }
+                }
+                Err(err) => print_error(&err),
+            }
+            result
+        }),
+        Command::List => {
+            list_days();
+            Ok(())
+        }
+        Command::Bench {
+            days,
+            part,
+            input,
+            params,
+            iterations,
+            warmup,
+            save,
+        } => days.days().and_then(|days| {
+            let source = input_source(input.as_deref(), &days)?;
+            let params = param_values(&params);
+            bench_days(
+                &days,
+                part,
+                &source,
+                &params,
+                iterations,
+                warmup,
+                save.as_deref(),
+            )
+        }),
+        Command::Verify { days, answers } => days.days().and_then(|days| {
+            let path = answers.unwrap_or_else(Answers::default_path);
+            let answers = Answers::load(&path).map_err(|err| {
+                print_error(&err);
+                "Failed to load the answers.".to_string()
+            })?;
+            verify_days(&days, &answers)
+        }),
+        Command::Day17 { command } => match command {
+            Day17Command::Debug {
+                input,
+                example,
+                script,
+                trace,
+            } => debug_program(
+                &program_source(input, example),
+                script.as_deref(),
+                trace.as_deref(),
+            ),
+            Day17Command::Disasm { input, example } => {
+                disassemble_program(&program_source(input, example))
+            }
+            Day17Command::Asm { source, output } => assemble_program(&source, output.as_deref()),
+        },
+        Command::New { day, title } => new_day(day, &title),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(msg) => {
+            eprintln!("Error: {}", msg);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn program_source(input: Option<PathBuf>, example: Option<String>) -> InputSource {
+    match (input, example) {
+        (Some(path), _) => InputSource::File(path),
+        (None, Some(example)) => InputSource::Example(example),
+        (None, None) => InputSource::Default,
+    }
+}
+
+fn debug_program(
+    source: &InputSource,
+    script: Option<&Path>,
+    trace: Option<&Path>,
+) -> Result<(), String> {
+    let day = day17::get_day();
+    let input = day.read_input_from(source).map_err(|err| {
+        print_error(&err);
+        "Failed to read the input.".to_string()
+    })?;
+
+    let mut out = io::stdout().lock();
+    let steps = match script {
+        Some(path) => match File::open(path) {
+            Ok(file) => debugger::debug(&input, BufReader::new(file), &mut out, false),
+            Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+        },
+        None => {
+            let interactive = io::stdin().is_terminal();
+            debugger::debug(&input, io::stdin().lock(), &mut out, interactive)
+        }
+    }
+    .map_err(|err| {
+        print_error(&with_input(err, &day.input_name(source), &input));
+        "Debugging failed.".to_string()
+    })?;
+
+    if let Some(path) = trace {
+        debugger::write_trace(path, &steps).map_err(|err| err.to_string())?;
+        println!(
+            "Wrote the trace of {} steps to {}.",
+            steps.len(),
+            path.display()
+        );
+    }
+    Ok(())
+}
+
+fn disassemble_program(source: &InputSource) -> Result<(), String> {
+    let day = day17::get_day();
+    let input = day.read_input_from(source).map_err(|err| {
+        print_error(&err);
+        "Failed to read the input.".to_string()
+    })?;
+
+    let text = asm::disassemble(&input).map_err(|err| {
+        print_error(&with_input(err, &day.input_name(source), &input));
+        "Failed to disassemble the program.".to_string()
+    })?;
+    print!("{}", text);
+    Ok(())
+}
+
+fn assemble_program(source: &Path, output: Option<&Path>) -> Result<(), String> {
+    let text = fs::read_to_string(source)
+        .map_err(|e| format!("Failed to read {}: {}", source.display(), e))?;
+
+    let input = asm::assemble(&text).map_err(|err| {
+        print_error(&with_input(err, &source.display().to_string(), &text));
+        "Failed to assemble the program.".to_string()
+    })?;
+    match output {
+        Some(path) => {
+            fs::write(path, format!("{}\n", input))
+                .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+            println!("Wrote the program to {}.", path.display());
+        }
+        None => println!("{}", input),
+    }
+    Ok(())
+}
+
+fn list_days() {
+    for day in days::all() {
+        let examples = day
+            .examples()
+            .iter()
+            .map(|example| example.name)
+            .collect::<Vec<_>>();
+        // parameters with different defaults per part are shown as part1/part2
+        let params = day
+            .params()
+            .iter()
+            .map(|param| match param.part1 == param.part2 {
+                true => format!("{}={}", param.name, param.part1),
+                false => format!("{}={}/{}", param.name, param.part1, param.part2),
+            })
+            .collect::<Vec<_>>();
+
+        print!(
+            "{:>2}  {:<24}  examples: {}",
+            day.number(),
+            day.title(),
+            examples.join(", ")
+        );
+        if !params.is_empty() {
+            print!("  params: {}", params.join(", "));
+        }
+        println!();
+    }
+
+    let missing = days::missing();
+    if !missing.is_empty() {
+        println!(
+            "Missing days: {}",
+            missing
+                .iter()
+                .map(|number| number.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+}
+
+fn run_days(
+    days: &[Day],
+    part: Option<Part>,
+    source: &InputSource,
+    params: &Params,
+) -> Result<(), String> {
+    println!("Advent of Code 2024");
+    println!();
+
+    let parts = selected_parts(part);
+    let mut summary = vec![];
+    let mut failed = 0;
+    for day in days {
+        let result = day
+            .solve(&parts, source, params)
+            .inspect_err(print_error)
+            .ok();
+        let solved = result
+            .as_ref()
+            .is_some_and(|result| result.parts.iter().all(|part| part.answer.is_ok()));
+        if !solved {
+            failed += 1;
+        }
+        summary.push((day, result));
+        println!();
+    }
+
+    if days.len() > 1 {
+        print_summary(&summary);
+    }
+
+    match failed {
+        0 => Ok(()),
+        n => Err(format!("{} day(s) failed.", n)),
+    }
+}
+
+// the widths of the summary columns: day, title, the answers of both parts, parse and time
+const SUMMARY_WIDTHS: [usize; 6] = [3, 24, 20, 20, 10, 10];
+const SUMMARY_SEPARATOR: &str = " | ";
+
+// the title is aligned to the left, the numbers to the right
+fn summary_row(cells: [String; 6]) -> String {
+    cells
+        .iter()
+        .zip(SUMMARY_WIDTHS)
+        .enumerate()
+        .map(|(column, (cell, width))| match column {
+            1 => format!("{:<width$}", cell),
+            _ => format!("{:>width$}", cell),
+        })
+        .collect::<Vec<_>>()
+        .join(SUMMARY_SEPARATOR)
+}
+
+fn print_summary(summary: &[(&Day, Option<DayResult>)]) {
+    println!(
+        "{}",
+        summary_row(["Day", "Title", "Part 1", "Part 2", "Parse", "Time"].map(String::from))
+    );
+    // the rule crosses the separators at their bar
+    let rule = SUMMARY_WIDTHS
+        .map(|width| "-".repeat(width + SUMMARY_SEPARATOR.len() - 1))
+        .join("+");
+    println!("{}", &rule[1..rule.len() - 1]);
+
+    let mut total_time = Duration::ZERO;
+    for (day, result) in summary {
+        let answer = |part| match result {
+            Some(result) => match result.parts.iter().find(|result| result.part == part) {
+                Some(result) => match &result.answer {
+                    Ok(answer) => answer.to_string(),
+                    Err(_) => "(failed)".to_string(),
+                },
+                None => String::new(),
+            },
+            None => "(no input)".to_string(),
+        };
+        let parse_time = match result.as_ref().and_then(|result| result.parse_time) {
+            Some(time) => format!("{:.2?}", time),
+            None => String::new(),
+        };
+        let time = result.as_ref().map(DayResult::time).unwrap_or_default();
+        total_time += time;
+        println!(
+            "{}",
+            summary_row([
+                day.number().to_string(),
+                day.title().to_string(),
+                answer(Part::One),
+                answer(Part::Two),
+                parse_time,
+                format!("{:.2?}", time),
+            ])
+        );
+    }
+    // the total time is aligned with the time column
+    let width =
+        SUMMARY_WIDTHS.iter().sum::<usize>() + SUMMARY_SEPARATOR.len() * (SUMMARY_WIDTHS.len() - 1);
+    println!("{:>width$}", format!("{:.2?}", total_time));
+}
+
+fn bench_days(
+    days: &[Day],
+    part: Option<Part>,
+    source: &InputSource,
+    params: &Params,
+    iterations: u32,
+    warmup: u32,
+    save: Option<&Path>,
+) -> Result<(), String> {
+    if cfg!(debug_assertions) {
+        println!("Note: this is a debug build, use --release for meaningful timings.");
+        println!();
+    }
+
+    let mut timings = vec![];
+    for day in days {
+        println!("{}", day);
+        let input = match day.read_input_from(source) {
+            Ok(input) => input,
+            Err(err) => {
+                print_error(&err);
+                println!();
+                continue;
+            }
+        };
+
+        if let Err(err) = day.parse(&input) {
+            println!("Parse: failed");
+            print_error(&with_input(err, &day.input_name(source), &input));
+            println!();
+            continue;
+        }
+        if day.parses() {
+            let stats = bench::measure(
+                || {
+                    let _ = day.parse(&input);
+                },
+                warmup,
+                iterations,
+            );
+            print_stats("Parse", &stats);
+            timings.push(Timing {
+                day: day.number(),
+                title: day.title().to_string(),
+                part: None,
+                stats,
+            });
+        }
+
+        for part in selected_parts(part) {
+            if let Err(err) = day.solve_part_with(part, &input, params) {
+                println!("{}: failed", part);
+                print_error(&with_input(err, &day.input_name(source), &input));
+                continue;
+            }
+
+            // every run solves a freshly parsed input, so a part can't reuse the results
+            // that an earlier run left in the parsed input
+            let stats = bench::measure_with(
+                || day.parse(&input).expect("the input was parsed before"),
+                |parsed| {
+                    let _ = day.solve_parsed(part, &mut **parsed, params);
+                },
+                warmup,
+                iterations,
+            );
+            print_stats(&part.to_string(), &stats);
+
+            timings.push(Timing {
+                day: day.number(),
+                title: day.title().to_string(),
+                part: Some(part),
+                stats,
+            });
+        }
+        println!();
+    }
+
+    if let Some(path) = save {
+        bench::write_table(path, &timings)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        println!("Timings written to {}.", path.display());
+    }
+    Ok(())
+}
+
+fn print_stats(label: &str, stats: &Stats) {
+    println!(
+        "{}: min {:.2?}, median {:.2?}, p95 {:.2?} ({} runs)",
+        label, stats.min, stats.median, stats.p95, stats.runs
+    );
+}
+
+fn verify_days(days: &[Day], answers: &Answers) -> Result<(), String> {
+    let (mut passed, mut failed, mut missing, mut unsolved) = (0, 0, 0, 0);
+
+    for day in days {
+        let inputs = day.inputs().unwrap_or_else(|err| {
+            print_error(&err);
+            vec![]
+        });
+        if inputs.is_empty() {
+            failed += Part::BOTH.len();
+            println!("Day {:>2}: no input", day.number());
+            continue;
+        }
+
+        for named_input in &inputs {
+            // only name the inputs of teammates, the output stays short with a single input
+            let label = match &named_input.name {
+                Some(name) => format!("Day {:>2} ({})", day.number(), name),
+                None => format!("Day {:>2}", day.number()),
+            };
+            let input = match day.read_input_from(&InputSource::File(named_input.path.clone())) {
+                Ok(input) => input,
+                Err(err) => {
+                    failed += Part::BOTH.len();
+                    print_error(&err);
+                    println!("{}: no input", label);
+                    continue;
+                }
+            };
+            let fingerprint = answers::fingerprint(&input);
+
+            for part in Part::BOTH {
+                let expected = answers.get(day.number(), &fingerprint, part);
+                // a panicking solver leaves nothing behind that the next part depends on
+                let result = panic::catch_unwind(AssertUnwindSafe(|| day.solve_part(part, &input)));
+                let status = match result {
+                    Ok(Ok(answer)) => {
+                        let verdict = Verdict::new(expected, &answer);
+                        match verdict {
+                            Verdict::Pass => passed += 1,
+                            Verdict::Fail { .. } => failed += 1,
+                            Verdict::Missing(_) => missing += 1,
+                            Verdict::Unsolved => unsolved += 1,
+                        }
+                        match verdict {
+                            Verdict::Missing(_) => format!(
+                                "{}, add it to [day{:0>2}.{}]",
+                                verdict,
+                                day.number(),
+                                fingerprint
+                            ),
+                            verdict => verdict.to_string(),
+                        }
+                    }
+                    Ok(Err(err)) => {
+                        failed += 1;
+                        let name = named_input.path.display().to_string();
+                        print_error(&with_input(err, &name, &input));
+                        "error".to_string()
+                    }
+                    Err(_) => {
+                        failed += 1;
+                        "panicked".to_string()
+                    }
+                };
+                println!("{} {}: {}", label, part, status);
+            }
+        }
+    }
+
+    println!();
+    println!(
+        "{} passed, {} failed, {} missing, {} unsolved",
+        passed, failed, missing, unsolved
+    );
+
+    match failed {
+        0 => Ok(()),
+        n => Err(format!("{} part(s) failed.", n)),
+    }
+}
+
+fn new_day(number: u8, title: &str) -> Result<(), String> {
+    let scaffold = scaffold::create_day(number, title).map_err(|err| {
+        print_error(&err);
+        format!("Failed to create day {}.", number)
+    })?;
+
+    for path in &scaffold.created {
+        println!("Created {}", path.display());
+    }
+    for path in &scaffold.changed {
+        println!("Updated {}", path.display());
+    }
+    println!(
+        "Paste the example into EXAMPLE in src/day{:0>2}.rs and your puzzle input into input/input_{:0>2}.txt.",
+        number, number
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numbers(selection: &str) -> Vec<u8> {
+        selection
+            .parse::<DaySelection>()
+            .unwrap()
+            .days()
+            .unwrap()
+            .iter()
+            .map(|day| day.number())
+            .collect()
+    }
+
+    #[test]
+    fn test_day_selection() {
+        assert_eq!(numbers("7"), vec![7]);
+        assert_eq!(numbers("10-13"), vec![10, 11, 13]);
+        assert_eq!(numbers("all").len(), days::all().len());
+    }
+
+    #[test]
+    fn test_invalid_day_selection() {
+        assert!("0".parse::<DaySelection>().is_err());
+        assert!("26".parse::<DaySelection>().is_err());
+        assert!("5-3".parse::<DaySelection>().is_err());
+        assert!("x".parse::<DaySelection>().is_err());
+        assert!("12".parse::<DaySelection>().unwrap().days().is_err());
+    }
+
+    #[test]
+    fn test_cli_arguments() {
+        assert!(Cli::try_parse_from(["aoc", "run", "1", "--part", "2"]).is_ok());
+        assert!(Cli::try_parse_from(["aoc", "run", "1", "--part", "3"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "run"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "run", "15", "--example", "larger"]).is_ok());
+        assert!(
+            Cli::try_parse_from(["aoc", "run", "1", "--example", "1", "--input", "-"]).is_err()
+        );
+        assert!(Cli::try_parse_from(["aoc", "new", "26"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "new", "20", "--title", "Race Condition"]).is_ok());
+        assert!(Cli::try_parse_from(["aoc", "run", "11", "--param", "blinks=500"]).is_ok());
+        assert!(Cli::try_parse_from(["aoc", "run", "11", "--param", "blinks"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_param() {
+        assert_eq!(parse_param("blinks=500"), Ok(("blinks".to_string(), 500)));
+        assert_eq!(
+            parse_param("offset=10_000_000_000_000"),
+            Ok(("offset".to_string(), 10000000000000))
+        );
+        assert!(parse_param("blinks=many").is_err());
+        assert!(parse_param("blinks").is_err());
+    }
+}
@@ -1,83 +1,89 @@
 use std::iter::zip;
 
-use crate::util::Day;
+use itertools::Itertools;
+use miette::Result;
+
+use crate::util::{parse, Answer, Day, Example, Params};
 
 const DAY_NR: u8 = 1;
 const PROBLEM_TITLE: &str = "Historian Hysteria";
 
 pub fn get_day() -> Day {
-    Day::new(DAY_NR, PROBLEM_TITLE, solve_part1, solve_part2)
+    Day::new(DAY_NR, PROBLEM_TITLE, solve_part1, solve_part2).with_examples(EXAMPLES)
 }
 
-fn solve_part1(input: &str) -> String {
-    let (mut left, mut right) = parse_input(input);
+fn solve_part1(input: &str, _params: &Params) -> Result<Answer> {
+    let (mut left, mut right) = parse_input(input)?;
 
     left.sort();
     right.sort();
 
-    zip(left, right)
+    Ok(zip(left, right)
         .map(|(l, r)| l.abs_diff(r))
         .sum::<u32>()
-        .to_string()
+        .into())
 }
 
-fn solve_part2(input: &str) -> String {
-    let (left, right) = parse_input(input);
+fn solve_part2(input: &str, _params: &Params) -> Result<Answer> {
+    let (left, right) = parse_input(input)?;
 
     let mut similarity = 0;
     for loc in left {
         similarity += loc * right.iter().filter(|n| **n == loc).count() as u32;
     }
 
-    similarity.to_string()
+    Ok(similarity.into())
 }
 
-fn parse_input(input: &str) -> (Vec<u32>, Vec<u32>) {
+fn parse_input(input: &str) -> Result<(Vec<u32>, Vec<u32>)> {
     let mut left_list: Vec<u32> = Vec::new();
     let mut right_list: Vec<u32> = Vec::new();
 
-    for line in input.lines() {
-        let parts: Vec<&str> = line.split_whitespace().collect();
-        left_list.push(parts[0].parse().expect("Error parsing integer"));
-        right_list.push(parts[1].parse().expect("Error parsing integer"));
+    for line in parse::lines(input) {
+        let (left, right) = line
+            .words()?
+            .into_iter()
+            .collect_tuple()
+            .ok_or_else(|| line.error("expected two location IDs"))?;
+        left_list.push(left);
+        right_list.push(right);
     }
 
-    (left_list, right_list)
+    Ok((left_list, right_list))
 }
 
+const EXAMPLE: &str = "3   4
+4   3
+2   5
+1   3
+3   9
+3   3";
+
+const EXAMPLES: &[Example] = &[Example::new("example", EXAMPLE).part1("11").part2("31")];
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::answers::assert_input_answer;
+    use crate::util::{assert_example_answers, Part};
 
     #[test]
     fn test_part1_with_examples() {
-        let input = r#"3   4
-            4   3
-            2   5
-            1   3
-            3   9
-            3   3"#;
-        assert_eq!(solve_part1(input), "11");
+        assert_example_answers(&get_day(), Part::One);
     }
 
     #[test]
     fn test_part1_with_input() {
-        assert_eq!(solve_part1(&get_day().read_input()), "1110981");
+        assert_input_answer(&get_day(), Part::One);
     }
 
     #[test]
     fn test_part2_with_examples() {
-        let input = r#"3   4
-            4   3
-            2   5
-            1   3
-            3   9
-            3   3"#;
-        assert_eq!(solve_part2(input), "31");
+        assert_example_answers(&get_day(), Part::Two);
     }
 
     #[test]
     fn test_part2_with_input() {
-        assert_eq!(solve_part2(&get_day().read_input()), "24869388");
+        assert_input_answer(&get_day(), Part::Two);
     }
 }
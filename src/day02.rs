@@ -1,28 +1,35 @@
-use crate::util::Day;
+use miette::Result;
+
+use crate::util::parse::{self, ParseResult};
+use crate::util::{Answer, Day, Example, Params};
 
 const DAY_NR: u8 = 2;
 const PROBLEM_TITLE: &str = "Red-Nosed Reports";
 
 pub fn get_day() -> Day {
-    Day::new(DAY_NR, PROBLEM_TITLE, solve_part1, solve_part2)
+    Day::new(DAY_NR, PROBLEM_TITLE, solve_part1, solve_part2).with_examples(EXAMPLES)
 }
 
-fn solve_part1(input: &str) -> String {
-    input
-        .lines()
-        .map(|line| line.trim())
-        .filter(|line| is_safe_1(line))
+fn solve_part1(input: &str, _params: &Params) -> Result<Answer> {
+    Ok(parse_reports(input)?
+        .iter()
+        .filter(|levels| is_safe_1(levels))
         .count()
-        .to_string()
+        .into())
 }
 
-fn solve_part2(input: &str) -> String {
-    input
-        .lines()
-        .map(|line| line.trim())
-        .filter(|line| is_safe_2(line))
+fn solve_part2(input: &str, _params: &Params) -> Result<Answer> {
+    Ok(parse_reports(input)?
+        .iter()
+        .filter(|levels| is_safe_2(levels))
         .count()
-        .to_string()
+        .into())
+}
+
+fn parse_reports(input: &str) -> Result<Vec<Vec<u16>>> {
+    Ok(parse::lines(input)
+        .map(|report| report.words())
+        .collect::<ParseResult<_>>()?)
 }
 
 fn exclude_element(values: &[u16], index: usize) -> Vec<u16> {
@@ -34,25 +41,15 @@ fn exclude_element(values: &[u16], index: usize) -> Vec<u16> {
         .collect()
 }
 
-fn is_safe_1(report: &str) -> bool {
-    let levels: Vec<u16> = report
-        .split_whitespace()
-        .map(|level| level.parse().expect("Error parsing integer"))
-        .collect();
-
-    is_safe(&levels)
+fn is_safe_1(levels: &[u16]) -> bool {
+    is_safe(levels)
 }
 
-fn is_safe_2(report: &str) -> bool {
-    let levels: Vec<u16> = report
-        .split_whitespace()
-        .map(|level| level.parse().expect("Error parsing integer"))
-        .collect();
-
+fn is_safe_2(levels: &[u16]) -> bool {
     let mut safe = false;
 
     for i in 0..levels.len() {
-        let reduced_levels = exclude_element(&levels, i);
+        let reduced_levels = exclude_element(levels, i);
         safe = safe || is_safe(&reduced_levels);
         if safe {
             break;
@@ -83,41 +80,38 @@ fn is_safe(values: &[u16]) -> bool {
     true
 }
 
+const EXAMPLE: &str = "7 6 4 2 1
+1 2 7 8 9
+9 7 6 2 1
+1 3 2 4 5
+8 6 4 4 1
+1 3 6 7 9";
+
+const EXAMPLES: &[Example] = &[Example::new("example", EXAMPLE).part1("2").part2("4")];
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::answers::assert_input_answer;
+    use crate::util::{assert_example_answers, Part};
 
     #[test]
     fn test_part1_with_examples() {
-        let input = r#"7 6 4 2 1
-            1 2 7 8 9
-            9 7 6 2 1
-            1 3 2 4 5
-            8 6 4 4 1
-            1 3 6 7 9"#;
-
-        assert_eq!(solve_part1(input), "2");
+        assert_example_answers(&get_day(), Part::One);
     }
 
     #[test]
     fn test_part1_with_input() {
-        assert_eq!(solve_part1(&get_day().read_input()), "463");
+        assert_input_answer(&get_day(), Part::One);
     }
 
     #[test]
     fn test_part2_with_examples() {
-        let input = r#"7 6 4 2 1
-            1 2 7 8 9
-            9 7 6 2 1
-            1 3 2 4 5
-            8 6 4 4 1
-            1 3 6 7 9"#;
-
-        assert_eq!(solve_part2(input), "4");
+        assert_example_answers(&get_day(), Part::Two);
     }
 
     #[test]
     fn test_part2_with_input() {
-        assert_eq!(solve_part2(&get_day().read_input()), "514");
+        assert_input_answer(&get_day(), Part::Two);
     }
 }
@@ -1,30 +1,32 @@
-use crate::util::Day;
+use crate::util::{Answer, Day, Example, Params};
+use log::trace;
+use miette::Result;
 use regex::Regex;
 
 const DAY_NR: u8 = 3;
 const PROBLEM_TITLE: &str = "Mull It Over";
 
 pub fn get_day() -> Day {
-    Day::new(DAY_NR, PROBLEM_TITLE, solve_part1, solve_part2)
+    Day::new(DAY_NR, PROBLEM_TITLE, solve_part1, solve_part2).with_examples(EXAMPLES)
 }
 
-fn solve_part1(input: &str) -> String {
+fn solve_part1(input: &str, _params: &Params) -> Result<Answer> {
     let mut result: u32 = 0;
     let re = Regex::new(r"mul\((\d{1,3}),(\d{1,3})\)").unwrap();
     for (_, [l, r]) in re.captures_iter(input).map(|c| c.extract()) {
         result += l.parse::<u32>().unwrap() * r.parse::<u32>().unwrap();
     }
 
-    result.to_string()
+    Ok(result.into())
 }
 
-fn solve_part2(input: &str) -> String {
+fn solve_part2(input: &str, _params: &Params) -> Result<Answer> {
     let mut result: u32 = 0;
     let re = Regex::new(r"(?:mul\((\d{1,3}),(\d{1,3})\))|(don't\(\))|(do\(\))").unwrap();
 
     let mut active = true;
     for c in re.captures_iter(input) {
-        dbg!(&c);
+        trace!("{}", &c[0]);
         match &c[0][..3] {
             "mul" => {
                 if active {
@@ -37,32 +39,42 @@ fn solve_part2(input: &str) -> String {
         }
     }
 
-    result.to_string()
+    Ok(result.into())
 }
 
+const EXAMPLE_MUL: &str = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
+
+const EXAMPLE_CONDITIONALS: &str =
+    "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
+const EXAMPLES: &[Example] = &[
+    Example::new("mul", EXAMPLE_MUL).part1("161"),
+    Example::new("conditionals", EXAMPLE_CONDITIONALS).part2("48"),
+];
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::answers::assert_input_answer;
+    use crate::util::{assert_example_answers, Part};
 
     #[test]
     fn test_part1_with_examples() {
-        let input = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
-        assert_eq!(solve_part1(input), "161");
+        assert_example_answers(&get_day(), Part::One);
     }
 
     #[test]
     fn test_part1_with_input() {
-        assert_eq!(solve_part1(&get_day().read_input()), "175700056");
+        assert_input_answer(&get_day(), Part::One);
     }
 
     #[test]
     fn test_part2_with_examples() {
-        let input = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
-        assert_eq!(solve_part2(input), "48");
+        assert_example_answers(&get_day(), Part::Two);
     }
 
     #[test]
     fn test_part2_with_input() {
-        assert_eq!(solve_part2(&get_day().read_input()), "71668682");
+        assert_input_answer(&get_day(), Part::Two);
     }
 }
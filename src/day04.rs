@@ -1,98 +1,58 @@
-use crate::util::Day;
+use miette::Result;
+
+use crate::util::{Answer, Day, Example, Grid, Params, Point};
 
 const DAY_NR: u8 = 4;
 const PROBLEM_TITLE: &str = "Ceres Search";
 
 pub fn get_day() -> Day {
-    Day::new(DAY_NR, PROBLEM_TITLE, solve_part1, solve_part2)
+    Day::new(DAY_NR, PROBLEM_TITLE, solve_part1, solve_part2).with_examples(EXAMPLES)
 }
 
-fn solve_part1(input: &str) -> String {
-    let xmas = "XMAS".as_bytes().to_vec();
-    let direction = [
-        [-1, 0],
-        [-1, -1],
-        [0, -1],
-        [1, -1],
-        [1, 0],
-        [1, 1],
-        [0, 1],
-        [-1, 1],
-    ];
-
-    let words = input
-        .lines()
-        .map(|line| line.as_bytes().to_vec())
-        .collect::<Vec<Vec<u8>>>();
-
-    let mut result = 0;
-
-    for x in 0..words.len() {
-        for y in 0..words[x].len() {
-            for dir in direction {
-                let mut found = true;
-                let mut search_x: i32 = x as i32;
-                let mut search_y: i32 = y as i32;
-
-                for ch in &xmas {
-                    if search_x >= 0
-                        && search_x < words.len() as i32
-                        && search_y >= 0
-                        && search_y < words[x].len() as i32
-                    {
-                        if words[search_x as usize][search_y as usize] != *ch {
-                            found = false;
-                            break;
-                        } else {
-                            search_x += dir[0];
-                            search_y += dir[1];
-                        }
-                    } else {
-                        found = false;
-                        break;
-                    }
-                }
-                if found {
-                    result += 1;
-                }
-            }
-        }
-    }
+fn solve_part1(input: &str, _params: &Params) -> Result<Answer> {
+    let words = Grid::parse(input, |ch| ch)?;
 
-    result.to_string()
-}
+    let mut result: u32 = 0;
 
-fn solve_part2(input: &str) -> String {
-    let mas = "MAS".as_bytes().to_vec();
-    let sam = "SAM".as_bytes().to_vec();
-
-    let words = input
-        .lines()
-        .map(|line| line.as_bytes().to_vec())
-        .collect::<Vec<Vec<u8>>>();
-
-    let mut result = 0;
-
-    for x in 1..words.len() - 1 {
-        for y in 1..words[x].len() - 1 {
-            let x1 = vec![words[x - 1][y - 1], words[x][y], words[x + 1][y + 1]];
-            let x2 = vec![words[x - 1][y + 1], words[x][y], words[x + 1][y - 1]];
-            if (x1 == mas || x1 == sam) && (x2 == mas || x2 == sam) {
+    for pos in words.find_all(|&ch| ch == 'X') {
+        for next in words.neighbours8(pos) {
+            let dir = next - pos;
+            let found = "XMAS"
+                .chars()
+                .enumerate()
+                .all(|(i, ch)| words.get(pos + i as i32 * dir) == Some(&ch));
+            if found {
                 result += 1;
             }
         }
     }
 
-    result.to_string()
+    Ok(result.into())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+fn solve_part2(input: &str, _params: &Params) -> Result<Answer> {
+    let words = Grid::parse(input, |ch| ch)?;
+
+    // the diagonal through pos from a to b reads MAS or SAM
+    let is_mas = |a: Point, b: Point| {
+        matches!(
+            (words.get(a), words.get(b)),
+            (Some('M'), Some('S')) | (Some('S'), Some('M'))
+        )
+    };
+
+    let result = words
+        .find_all(|&ch| ch == 'A')
+        .filter(|&pos| {
+            is_mas(pos + Point::new(-1, -1), pos + Point::new(1, 1))
+                && is_mas(pos + Point::new(1, -1), pos + Point::new(-1, 1))
+        })
+        .count();
+
+    Ok(result.into())
+}
 
-    #[test]
-    fn test_part1_with_examples() {
-        let input = "MMMSXXMASM
+const EXAMPLE: &str = "MMMSXXMASM
 MSAMXMSMSA
 AMXSXMAAMM
 MSAMASMSMX
@@ -102,39 +62,39 @@ SMSMSASXSS
 SAXAMASAAA
 MAMMMXMMMM
 MXMXAXMASX";
-        assert_eq!(solve_part1(input), "18");
-    }
 
-    #[test]
-    fn test_part1_with_simple_example() {
-        let input = "MMXMASM
+const EXAMPLE_SMALL: &str = "MMXMASM
 MMMMMMM
 MSAMXAA";
-        assert_eq!(solve_part1(input), "2");
+
+const EXAMPLES: &[Example] = &[
+    Example::new("example", EXAMPLE).part1("18").part2("9"),
+    Example::new("small", EXAMPLE_SMALL).part1("2"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answers::assert_input_answer;
+    use crate::util::{assert_example_answers, Part};
+
+    #[test]
+    fn test_part1_with_examples() {
+        assert_example_answers(&get_day(), Part::One);
     }
 
     #[test]
     fn test_part1_with_input() {
-        assert_eq!(solve_part1(&get_day().read_input()), "2483");
+        assert_input_answer(&get_day(), Part::One);
     }
 
     #[test]
     fn test_part2_with_examples() {
-        let input = "MMMSXXMASM
-MSAMXMSMSA
-AMXSXMAAMM
-MSAMASMSMX
-XMASAMXAMM
-XXAMMXXAMA
-SMSMSASXSS
-SAXAMASAAA
-MAMMMXMMMM
-MXMXAXMASX";
-        assert_eq!(solve_part2(input), "9");
+        assert_example_answers(&get_day(), Part::Two);
     }
 
     #[test]
     fn test_part2_with_input() {
-        assert_eq!(solve_part2(&get_day().read_input()), "1925");
+        assert_input_answer(&get_day(), Part::Two);
     }
 }
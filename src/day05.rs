@@ -1,12 +1,15 @@
 use std::cmp::Ordering;
 
-use crate::util::Day;
+use miette::{bail, Result};
+
+use crate::util::parse::{self, ParseResult};
+use crate::util::{Answer, Day, Example, Params};
 
 const DAY_NR: u8 = 5;
 const PROBLEM_TITLE: &str = "Print Queue";
 
 pub fn get_day() -> Day {
-    Day::new(DAY_NR, PROBLEM_TITLE, solve_part1, solve_part2)
+    Day::new(DAY_NR, PROBLEM_TITLE, solve_part1, solve_part2).with_examples(EXAMPLES)
 }
 
 #[derive(Debug)]
@@ -48,8 +51,8 @@ impl Update {
     }
 }
 
-fn solve_part1(input: &str) -> String {
-    let (order_rules, updates) = parse_input(input);
+fn solve_part1(input: &str, _params: &Params) -> Result<Answer> {
+    let (order_rules, updates) = parse_input(input)?;
 
     let mut result: u32 = 0;
     for update in updates {
@@ -58,7 +61,7 @@ fn solve_part1(input: &str) -> String {
         }
     }
 
-    result.to_string()
+    Ok(result.into())
 }
 
 fn cmp(order_rules: &Vec<OrderRule>, a: &u32, b: &u32) -> Ordering {
@@ -72,8 +75,8 @@ fn cmp(order_rules: &Vec<OrderRule>, a: &u32, b: &u32) -> Ordering {
     panic!("no rule found");
 }
 
-fn solve_part2(input: &str) -> String {
-    let (order_rules, updates) = parse_input(input);
+fn solve_part2(input: &str, _params: &Params) -> Result<Answer> {
+    let (order_rules, updates) = parse_input(input)?;
 
     let mut result: u32 = 0;
     for update in updates {
@@ -84,44 +87,39 @@ fn solve_part2(input: &str) -> String {
         }
     }
 
-    result.to_string()
+    Ok(result.into())
 }
 
-fn parse_input(input: &str) -> (Vec<OrderRule>, Vec<Update>) {
-    let parts = input.split("\n\n").collect::<Vec<&str>>();
+fn parse_input(input: &str) -> Result<(Vec<OrderRule>, Vec<Update>)> {
+    let sections = parse::sections(input);
+    let [rules_section, updates_section] = sections[..] else {
+        bail!("Expected order rules and updates separated by a blank line.");
+    };
 
-    let order_rules: Vec<OrderRule> = parts[0]
+    let order_rules = rules_section
         .lines()
         .map(|rule| {
-            let pages = rule.split('|').collect::<Vec<&str>>();
-            OrderRule {
-                before: pages[0].parse::<u32>().unwrap(),
-                after: pages[1].parse::<u32>().unwrap(),
-            }
+            let (before, after) = rule.split_once("|")?;
+            Ok(OrderRule {
+                before: before.parse()?,
+                after: after.parse()?,
+            })
         })
-        .collect();
+        .collect::<ParseResult<Vec<OrderRule>>>()?;
 
-    let updates: Vec<Update> = parts[1]
+    let updates = updates_section
         .lines()
         .map(|line| {
-            let pages = line
-                .split(',')
-                .map(|page| page.parse::<u32>().unwrap())
-                .collect::<Vec<u32>>();
-            Update { pages }
+            Ok(Update {
+                pages: line.list(',')?,
+            })
         })
-        .collect();
+        .collect::<ParseResult<Vec<Update>>>()?;
 
-    (order_rules, updates)
+    Ok((order_rules, updates))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part1_with_examples() {
-        let input = "47|53
+const EXAMPLE: &str = "47|53
 97|13
 97|61
 97|47
@@ -150,50 +148,32 @@ mod tests {
 61,13,29
 97,13,75,29,47
 ";
-        assert_eq!(solve_part1(input), "143");
+
+const EXAMPLES: &[Example] = &[Example::new("example", EXAMPLE).part1("143").part2("123")];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answers::assert_input_answer;
+    use crate::util::{assert_example_answers, Part};
+
+    #[test]
+    fn test_part1_with_examples() {
+        assert_example_answers(&get_day(), Part::One);
     }
 
     #[test]
     fn test_part1_with_input() {
-        assert_eq!(solve_part1(&get_day().read_input()), "6051");
+        assert_input_answer(&get_day(), Part::One);
     }
 
     #[test]
     fn test_part2_with_examples() {
-        let input = "47|53
-97|13
-97|61
-97|47
-75|29
-61|13
-75|53
-29|13
-97|29
-53|29
-61|53
-97|53
-61|29
-47|13
-75|47
-97|75
-47|61
-75|61
-47|29
-75|13
-53|13
-
-75,47,61,53,29
-97,61,53,29,13
-75,29,13
-75,97,47,61,53
-61,13,29
-97,13,75,29,47
-";
-        assert_eq!(solve_part2(input), "123");
+        assert_example_answers(&get_day(), Part::Two);
     }
 
     #[test]
     fn test_part2_with_input() {
-        assert_eq!(solve_part2(&get_day().read_input()), "5093");
+        assert_input_answer(&get_day(), Part::Two);
     }
 }
@@ -1,13 +1,16 @@
-use core::str;
 use std::collections::HashSet;
 
-use crate::util::Day;
+use miette::{bail, miette, Result};
+
+use crate::util::record;
+use crate::util::render::{self, Canvas, Render, Style};
+use crate::util::{Answer, Day, Direction, Example, Grid, Params, Point};
 
 const DAY_NR: u8 = 6;
 const PROBLEM_TITLE: &str = "Guard Gallivant";
 
 pub fn get_day() -> Day {
-    Day::new(DAY_NR, PROBLEM_TITLE, solve_part1, solve_part2)
+    Day::new(DAY_NR, PROBLEM_TITLE, solve_part1, solve_part2).with_examples(EXAMPLES)
 }
 
 const GUARD: u8 = b'^';
@@ -15,13 +18,16 @@ const OBSTRUCTION: u8 = b'#';
 const MARK: u8 = b'X';
 const EMPTY: u8 = b'.';
 
-fn solve_part1(input: &str) -> String {
-    let mut map = Map::new(input);
-    let mut guard = map.get_guard().expect("No guard found in map.");
+fn solve_part1(input: &str, _params: &Params) -> Result<Answer> {
+    let mut map = Map::new(input)?;
+    let mut guard = map
+        .get_guard()
+        .ok_or_else(|| miette!("No guard found in map."))?;
 
     let mut visited: u32 = 1;
 
     while guard.is_in_map() {
+        record::frame(&guard);
         if guard.try_move() {
             if guard.visit() {
                 visited += 1;
@@ -30,77 +36,69 @@ fn solve_part1(input: &str) -> String {
             guard.turn_right();
         }
     }
+    render::show("Visited positions", &map);
 
-    visited.to_string()
+    Ok(visited.into())
 }
 
-fn solve_part2(input: &str) -> String {
-    let mut result = 0;
+fn solve_part2(input: &str, _params: &Params) -> Result<Answer> {
+    let mut result: u32 = 0;
 
-    let map = Map::new(input);
+    let mut map = Map::new(input)?;
+    if map.get_guard().is_none() {
+        return Err(miette!("No guard found in map."));
+    }
 
-    for row in 0..map.height() {
-        for col in 0..map.width() {
-            let mut test_map = Map::new(input);
-            if test_map.place_obstruction(row, col) && test_map.check_cycle() {
-                result += 1;
-            }
+    for pos in map.map.positions() {
+        let mut test_map = Map::new(input)?;
+        if test_map.place_obstruction(pos) && test_map.check_cycle() {
+            result += 1;
         }
     }
 
-    result.to_string()
+    Ok(result.into())
 }
 
 #[derive(Debug)]
 struct Map {
-    map: Vec<Vec<u8>>,
+    map: Grid<u8>,
 }
 
 impl Map {
-    fn new(s: &str) -> Map {
-        Map {
-            map: s
-                .lines()
-                .map(|line| line.as_bytes().to_vec())
-                .collect::<Vec<_>>(),
-        }
-    }
-
-    fn get_guard(&mut self) -> Option<Guard> {
-        for row in 0..self.height() {
-            for col in 0..self.width() {
-                if self.map[row][col] == GUARD {
-                    self.mark(row as i32, col as i32);
-                    return Some(Guard::new(self, row as i32, col as i32));
-                }
-            }
-        }
-        None
-    }
-
-    fn height(&self) -> usize {
-        self.map.len()
+    fn new(s: &str) -> Result<Map> {
+        Ok(Map {
+            map: Grid::try_parse(s, |ch| match u8::try_from(ch) {
+                Ok(tile @ (EMPTY | OBSTRUCTION | GUARD)) => Ok(tile),
+                _ => bail!(
+                    help = "The map consists of '.', '#' and the guard '^'.",
+                    "unexpected map character {:?}",
+                    ch
+                ),
+            })?,
+        })
     }
 
-    fn width(&self) -> usize {
-        self.map[0].len()
+    fn get_guard(&mut self) -> Option<Guard<'_>> {
+        let pos = self.map.find(|&tile| tile == GUARD)?;
+        self.mark(pos);
+        Some(Guard::new(self, pos))
     }
 
-    fn is_obstruction(&self, row: i32, col: i32) -> bool {
-        self.map[row as usize][col as usize] == OBSTRUCTION
+    fn is_obstruction(&self, pos: Point) -> bool {
+        self.map[pos] == OBSTRUCTION
     }
 
-    fn place_obstruction(&mut self, row: usize, col: usize) -> bool {
-        if self.map[row][col] == EMPTY {
-            self.map[row][col] = OBSTRUCTION;
+    fn place_obstruction(&mut self, pos: Point) -> bool {
+        if self.map[pos] == EMPTY {
+            self.map[pos] = OBSTRUCTION;
             return true;
         }
         false
     }
 
-    fn mark(&mut self, row: i32, col: i32) -> bool {
-        if self.map[row as usize][col as usize] != MARK {
-            self.map[row as usize][col as usize] = MARK;
+    fn mark(&mut self, pos: Point) -> bool {
+        if self.map[pos] != MARK {
+            self.map[pos] = MARK;
             return true;
         }
         false
@@ -127,52 +125,41 @@ impl Map {
 
         true
     }
-
-    // fn print(&self) {
-    //     for row in &self.map {
-    //         println!("{}", String::from_utf8(row.clone()).unwrap());
-    //     }
-    // }
 }
 
-const DIRECTIONS: [[i32; 2]; 4] = [
-    [-1, 0], // up
-    [0, 1],  // right
-    [1, 0],  // down
-    [0, -1], // left
-];
+impl Render for Map {
+    fn canvas(&self) -> Canvas {
+        Canvas::new(&self.map, |&tile| tile as char)
+            .visited(self.map.find_all(|&tile| tile == MARK))
+    }
+}
 
 struct Guard<'a> {
     map: &'a mut Map,
-    row: i32,
-    col: i32,
-    dir: usize, // index in DIRECTIONS array
+    pos: Point,
+    dir: Direction,
 }
 
 impl Guard<'_> {
-    fn new(map: &mut Map, row: i32, col: i32) -> Guard {
+    fn new(map: &mut Map, pos: Point) -> Guard<'_> {
         Guard {
             map,
-            row,
-            col,
-            dir: 0,
+            pos,
+            dir: Direction::Up,
         }
     }
 
     fn try_move(&mut self) -> bool {
-        let old_row = self.row;
-        let old_col = self.col;
+        let old_pos = self.pos;
 
-        self.row += DIRECTIONS[self.dir][0];
-        self.col += DIRECTIONS[self.dir][1];
+        self.pos += self.dir;
 
         if !self.is_in_map() {
             return false;
         }
 
-        if self.map.is_obstruction(self.row, self.col) {
-            self.row = old_row;
-            self.col = old_col;
+        if self.map.is_obstruction(self.pos) {
+            self.pos = old_pos;
             return false;
         }
 
@@ -180,32 +167,31 @@ impl Guard<'_> {
     }
 
     fn turn_right(&mut self) {
-        self.dir = (self.dir + 1) % 4;
+        self.dir = self.dir.turn_right();
     }
 
     fn is_in_map(&self) -> bool {
-        self.row >= 0
-            && self.row < self.map.height() as i32
-            && self.col >= 0
-            && self.col < self.map.width() as i32
+        self.map.map.contains(self.pos)
     }
 
     fn visit(&mut self) -> bool {
-        self.map.mark(self.row, self.col)
+        self.map.mark(self.pos)
     }
 
-    fn pos_and_dir(&self) -> ((i32, i32), usize) {
-        ((self.row, self.col), self.dir)
+    fn pos_and_dir(&self) -> (Point, Direction) {
+        (self.pos, self.dir)
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl Render for Guard<'_> {
+    fn canvas(&self) -> Canvas {
+        let mut canvas = self.map.canvas();
+        canvas.set(self.pos, self.dir.arrow(), Style::Actor);
+        canvas
+    }
+}
 
-    #[test]
-    fn test_part1_with_examples() {
-        let input = "....#.....
+const EXAMPLE: &str = "....#.....
 .........#
 ..........
 ..#.......
@@ -215,31 +201,42 @@ mod tests {
 ........#.
 #.........
 ......#...";
-        assert_eq!(solve_part1(input), "41");
+
+const EXAMPLES: &[Example] = &[Example::new("example", EXAMPLE).part1("41").part2("6")];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answers::assert_input_answer;
+    use crate::util::{assert_example_answers, Part};
+
+    #[test]
+    fn test_part1_with_examples() {
+        assert_example_answers(&get_day(), Part::One);
     }
 
     #[test]
     fn test_part1_with_input() {
-        assert_eq!(solve_part1(&get_day().read_input()), "4967");
+        assert_input_answer(&get_day(), Part::One);
     }
 
     #[test]
     fn test_part2_with_examples() {
-        let input = "....#.....
-.........#
-..........
-..#.......
-.......#..
-..........
-.#..^.....
-........#.
-#.........
-......#...";
-        assert_eq!(solve_part2(input), "6");
+        assert_example_answers(&get_day(), Part::Two);
     }
 
     #[test]
     fn test_part2_with_input() {
-        assert_eq!(solve_part2(&get_day().read_input()), "1789");
+        assert_input_answer(&get_day(), Part::Two);
+    }
+
+    #[test]
+    fn test_unknown_tile() {
+        let error = Map::new("..#\n.^x").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 3: unexpected map character 'x'"
+        );
+        assert!(error.help().is_some());
     }
 }
@@ -2,13 +2,16 @@ use std::iter::repeat_n;
 
 use itertools::Itertools;
 
-use crate::util::Day;
+use miette::Result;
+
+use crate::util::parse::{self, ParseResult, Span};
+use crate::util::{Answer, Day, Example, Params};
 
 const DAY_NR: u8 = 7;
 const PROBLEM_TITLE: &str = "Bridge Repair";
 
 pub fn get_day() -> Day {
-    Day::new(DAY_NR, PROBLEM_TITLE, solve_part1, solve_part2)
+    Day::new(DAY_NR, PROBLEM_TITLE, solve_part1, solve_part2).with_examples(EXAMPLES)
 }
 
 type Num = u64;
@@ -20,19 +23,16 @@ struct Equation {
 }
 
 impl Equation {
-    fn new(s: &str) -> Self {
-        let parts = s.split(": ").collect::<Vec<&str>>();
+    fn new(line: Span) -> ParseResult<Self> {
+        let (test_value, numbers) = line.key_value()?;
 
-        let test_value: Num = parts[0].parse().expect("Error parsing integer.");
-        let numbers: Vec<Num> = parts[1]
-            .split_whitespace()
-            .map(|n| n.parse().expect("Error parsing integer."))
-            .collect();
+        let test_value = test_value.parse()?;
+        let numbers = numbers.words()?;
 
-        Equation {
+        Ok(Equation {
             test_value,
             numbers,
-        }
+        })
     }
 
     fn can_be_true(&self, is_part1: bool) -> bool {
@@ -68,33 +68,30 @@ impl Equation {
     }
 }
 
-fn solve_part1(input: &str) -> String {
+fn solve_part1(input: &str, _params: &Params) -> Result<Answer> {
     solve(input, true)
 }
 
-fn solve_part2(input: &str) -> String {
+fn solve_part2(input: &str, _params: &Params) -> Result<Answer> {
     solve(input, false)
 }
 
-fn solve(input: &str, is_part1: bool) -> String {
-    let equations: Vec<Equation> = input.lines().map(Equation::new).collect();
+fn solve(input: &str, is_part1: bool) -> Result<Answer> {
+    let equations = parse::lines(input)
+        .map(Equation::new)
+        .collect::<ParseResult<Vec<Equation>>>()?;
 
-    equations
+    Ok(equations
         .into_iter()
         .map(|e| match e.can_be_true(is_part1) {
             true => e.test_value,
             false => 0,
         })
         .sum::<Num>()
-        .to_string()
+        .into())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    fn example() -> &'static str {
-        "190: 10 19
+const EXAMPLE: &str = "190: 10 19
 3267: 81 40 27
 83: 17 5
 156: 15 6
@@ -102,26 +99,35 @@ mod tests {
 161011: 16 10 13
 192: 17 8 14
 21037: 9 7 18 13
-292: 11 6 16 20"
-    }
+292: 11 6 16 20";
+
+const EXAMPLES: &[Example] = &[Example::new("example", EXAMPLE)
+    .part1("3749")
+    .part2("11387")];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answers::assert_input_answer;
+    use crate::util::{assert_example_answers, Part};
 
     #[test]
     fn test_part1_with_examples() {
-        assert_eq!(solve_part1(example()), "3749");
+        assert_example_answers(&get_day(), Part::One);
     }
 
     #[test]
     fn test_part1_with_input() {
-        assert_eq!(solve_part1(&get_day().read_input()), "850435817339");
+        assert_input_answer(&get_day(), Part::One);
     }
 
     #[test]
     fn test_part2_with_examples() {
-        assert_eq!(solve_part2(example()), "11387");
+        assert_example_answers(&get_day(), Part::Two);
     }
 
     #[test]
     fn test_part2_with_input() {
-        assert_eq!(solve_part2(&get_day().read_input()), "104824810233437");
+        assert_input_answer(&get_day(), Part::Two);
     }
 }
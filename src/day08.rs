@@ -1,204 +1,111 @@
-use crate::util::Day;
-use itertools::Itertools;
 use std::collections::{HashMap, HashSet};
 
+use itertools::Itertools;
+use log::{debug, trace};
+use miette::Result;
+
+use crate::util::render::{self, Canvas, Render, Style};
+use crate::util::{Answer, Day, Example, Grid, Params, Point};
+
 const DAY_NR: u8 = 8;
 const PROBLEM_TITLE: &str = "Resonant Collinearity";
 
 pub fn get_day() -> Day {
-    Day::new(DAY_NR, PROBLEM_TITLE, solve_part1, solve_part2)
-}
-
-#[derive(Eq, Hash, PartialEq, Debug, Clone, Copy)]
-struct Pos {
-    row: i32,
-    col: i32,
+    Day::new(DAY_NR, PROBLEM_TITLE, solve_part1, solve_part2).with_examples(EXAMPLES)
 }
 
-fn solve_part1(input: &str) -> String {
-    let mut map: HashMap<char, HashSet<Pos>> = HashMap::new();
-
-    let mut row = 0;
-    for line in input.lines() {
-        for (col, ch) in line.chars().enumerate() {
-            match ch {
-                '.' => continue,
-                _ => match map.get_mut(&ch) {
-                    Some(list) => list.insert(Pos {
-                        row,
-                        col: col.try_into().unwrap(),
-                    }),
-                    None => {
-                        map.insert(ch, HashSet::new());
-                        map.get_mut(&ch).unwrap().insert(Pos {
-                            row,
-                            col: col.try_into().unwrap(),
-                        })
-                    }
-                },
-            };
-        }
-        row += 1;
-    }
-
-    let height = row;
-    let width = input.lines().next().unwrap().as_bytes().len() as i32;
+fn solve_part1(input: &str, _params: &Params) -> Result<Answer> {
+    let map = Grid::parse(input, |ch| ch)?;
+    debug!("Height: {} Width: {}", map.height(), map.width());
 
-    //println!("{:?}", map);
-    println!("Height: {} Width: {}", height, width);
-
-    let mut antinodes: HashSet<Pos> = HashSet::new();
-    for (freq, positions) in map.iter() {
-        println!("Frequency: {}", freq);
+    let mut antinodes: HashSet<Point> = HashSet::new();
+    for (freq, positions) in antennas(&map) {
+        debug!("Frequency: {}", freq);
         for pair in positions.iter().combinations(2) {
-            print!("Pair {:?}: ", pair);
-            let anti1 = Pos {
-                row: pair[0].row + 2 * (pair[1].row - pair[0].row),
-                col: pair[0].col + 2 * (pair[1].col - pair[0].col),
-            };
-            print!("Anti1: {:?} ", anti1);
-            if anti1.row >= 0 && anti1.row < height && anti1.col >= 0 && anti1.col < width {
-                antinodes.insert(anti1);
-            } else {
-                print!("(out) ");
-            }
-            let anti2 = Pos {
-                row: pair[1].row + 2 * (pair[0].row - pair[1].row),
-                col: pair[1].col + 2 * (pair[0].col - pair[1].col),
-            };
-            print!("Anti2: {:?} ", anti2);
-            if anti2.row >= 0 && anti2.row < height && anti2.col >= 0 && anti2.col < width {
-                antinodes.insert(anti2);
-            } else {
-                print!("(out) ");
-            }
-            println!();
-        }
-        println!();
-    }
-    println!("Antinodes: {:?}", antinodes);
-
-    for row in 0..height {
-        for col in 0..width {
-            if antinodes.contains(&Pos { row, col }) {
-                print!("#");
-            } else {
-                print!(".");
+            let anti1 = pair[0] + 2 * (pair[1] - pair[0]);
+            let anti2 = pair[1] + 2 * (pair[0] - pair[1]);
+            trace!(
+                "Pair {:?}: Anti1: {:?}{} Anti2: {:?}{}",
+                pair,
+                anti1,
+                out_marker(&map, anti1),
+                anti2,
+                out_marker(&map, anti2)
+            );
+            for anti in [anti1, anti2] {
+                if map.contains(anti) {
+                    antinodes.insert(anti);
+                }
             }
         }
-        println!();
     }
+    render::show("Antinodes", &Antinodes(&map, &antinodes));
 
-    antinodes.len().to_string()
+    Ok(antinodes.len().into())
 }
 
-fn solve_part2(input: &str) -> String {
-    let mut map: HashMap<char, HashSet<Pos>> = HashMap::new();
-
-    let mut row = 0;
-    for line in input.lines() {
-        for (col, ch) in line.chars().enumerate() {
-            match ch {
-                '.' => continue,
-                _ => match map.get_mut(&ch) {
-                    Some(list) => list.insert(Pos {
-                        row,
-                        col: col.try_into().unwrap(),
-                    }),
-                    None => {
-                        map.insert(ch, HashSet::new());
-                        map.get_mut(&ch).unwrap().insert(Pos {
-                            row,
-                            col: col.try_into().unwrap(),
-                        })
-                    }
-                },
-            };
-        }
-        row += 1;
-    }
-
-    let height = row;
-    let width = input.lines().next().unwrap().as_bytes().len() as i32;
-
-    //println!("{:?}", map);
-    println!("Height: {} Width: {}", height, width);
+fn solve_part2(input: &str, _params: &Params) -> Result<Answer> {
+    let map = Grid::parse(input, |ch| ch)?;
+    debug!("Height: {} Width: {}", map.height(), map.width());
 
-    let mut antinodes: HashSet<Pos> = HashSet::new();
-    for (freq, positions) in map.iter() {
-        println!("Frequency: {}", freq);
+    let mut antinodes: HashSet<Point> = HashSet::new();
+    for (freq, positions) in antennas(&map) {
+        debug!("Frequency: {}", freq);
         for pair in positions.iter().combinations(2) {
-            print!("Pair {:?}: ", pair);
-
-            let row_diff = pair[1].row - pair[0].row;
-            let col_diff = pair[1].col - pair[0].col;
-
-            let origin = Pos {
-                row: pair[0].row,
-                col: pair[0].col,
-            };
+            let diff = pair[1] - pair[0];
+            let origin = *pair[0];
             antinodes.insert(origin);
 
-            let mut stop = false;
-            let mut n = 0;
-            while !stop {
-                let anti = Pos {
-                    row: origin.row + n * row_diff,
-                    col: origin.col + n * col_diff,
-                };
-                print!("Anti: {:?} ", anti);
-                if anti.row >= 0 && anti.row < height && anti.col >= 0 && anti.col < width {
+            for step in [diff, -diff] {
+                let mut anti = origin;
+                while map.contains(anti) {
+                    trace!("Pair {:?}: Anti: {:?}", pair, anti);
                     antinodes.insert(anti);
-                } else {
-                    print!("(out) ");
-                    stop = true;
+                    anti += step;
                 }
-                println!();
-                n += 1;
-            }
-
-            n = 0;
-            stop = false;
-            while !stop {
-                let anti = Pos {
-                    row: origin.row - n * row_diff,
-                    col: origin.col - n * col_diff,
-                };
-                print!("Anti: {:?} ", anti);
-                if anti.row >= 0 && anti.row < height && anti.col >= 0 && anti.col < width {
-                    antinodes.insert(anti);
-                } else {
-                    print!("(out) ");
-                    stop = true;
-                }
-                println!();
-                n += 1;
             }
         }
-        println!();
     }
-    println!("Antinodes: {:?}", antinodes);
-
-    for row in 0..height {
-        for col in 0..width {
-            if antinodes.contains(&Pos { row, col }) {
-                print!("#");
-            } else {
-                print!(".");
-            }
+    render::show("Antinodes", &Antinodes(&map, &antinodes));
+
+    Ok(antinodes.len().into())
+}
+
+// positions of the antennas by frequency
+fn antennas(map: &Grid<char>) -> HashMap<char, Vec<Point>> {
+    let mut antennas: HashMap<char, Vec<Point>> = HashMap::new();
+    for (pos, &ch) in map.iter() {
+        if ch != '.' {
+            antennas.entry(ch).or_default().push(pos);
         }
-        println!();
     }
+    antennas
+}
 
-    antinodes.len().to_string()
+fn out_marker(map: &Grid<char>, pos: Point) -> &'static str {
+    match map.contains(pos) {
+        true => "",
+        false => " (out)",
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+// the map with the antinodes highlighted
+struct Antinodes<'a>(&'a Grid<char>, &'a HashSet<Point>);
 
-    fn example() -> &'static str {
-        "............
+impl Render for Antinodes<'_> {
+    fn canvas(&self) -> Canvas {
+        let Antinodes(map, antinodes) = self;
+        let mut canvas = map.canvas();
+        for &pos in antinodes.iter() {
+            if map[pos] == '.' {
+                canvas.set(pos, '#', Style::Highlight);
+            }
+        }
+        canvas.highlight(antinodes.iter().copied())
+    }
+}
+
+const EXAMPLE: &str = "............
 ........0...
 .....0......
 .......0....
@@ -209,11 +116,9 @@ mod tests {
 ........A...
 .........A..
 ............
-............"
-    }
+............";
 
-    fn example2() -> &'static str {
-        "T.........
+const EXAMPLE_T_ANTENNAS: &str = "T.........
 ...T......
 .T........
 ..........
@@ -222,27 +127,36 @@ mod tests {
 ..........
 ..........
 ..........
-.........."
-    }
+..........";
+
+const EXAMPLES: &[Example] = &[
+    Example::new("example", EXAMPLE).part1("14").part2("34"),
+    Example::new("t-antennas", EXAMPLE_T_ANTENNAS).part2("9"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answers::assert_input_answer;
+    use crate::util::{assert_example_answers, Part};
 
     #[test]
     fn test_part1_with_examples() {
-        assert_eq!(solve_part1(example()), "14");
+        assert_example_answers(&get_day(), Part::One);
     }
 
     #[test]
     fn test_part1_with_input() {
-        assert_eq!(solve_part1(&get_day().read_input()), "17");
+        assert_input_answer(&get_day(), Part::One);
     }
 
     #[test]
     fn test_part2_with_examples() {
-        assert_eq!(solve_part2(example()), "34");
-        assert_eq!(solve_part2(example2()), "9");
+        assert_example_answers(&get_day(), Part::Two);
     }
 
     #[test]
     fn test_part2_with_input() {
-        assert_eq!(solve_part2(&get_day().read_input()), "42");
+        assert_input_answer(&get_day(), Part::Two);
     }
 }
@@ -1,24 +1,27 @@
-use crate::util::Day;
+use miette::{bail, Result};
+
+use crate::util::parse::{self, ParseResult};
+use crate::util::{Answer, Day, Example, Params};
 
 const DAY_NR: u8 = 9;
 const PROBLEM_TITLE: &str = "Disk Fragmenter";
 
 pub fn get_day() -> Day {
-    Day::new(DAY_NR, PROBLEM_TITLE, solve_part1, solve_part2)
+    Day::new(DAY_NR, PROBLEM_TITLE, solve_part1, solve_part2).with_examples(EXAMPLES)
 }
 
-fn solve_part1(input: &str) -> String {
-    let mut disk_map = DiskMap::new(input);
+fn solve_part1(input: &str, _params: &Params) -> Result<Answer> {
+    let mut disk_map = DiskMap::new(input)?;
     disk_map.create_block_map();
     disk_map.fragment_block_map();
-    disk_map.checksum().to_string()
+    Ok(disk_map.checksum().into())
 }
 
-fn solve_part2(input: &str) -> String {
-    let mut disk_map = DiskMap::new(input);
+fn solve_part2(input: &str, _params: &Params) -> Result<Answer> {
+    let mut disk_map = DiskMap::new(input)?;
     disk_map.create_block_map();
     disk_map.defragment_block_map();
-    disk_map.checksum().to_string()
+    Ok(disk_map.checksum().into())
 }
 
 const FREE: i32 = -1;
@@ -31,15 +34,37 @@ struct DiskMap {
 }
 
 impl DiskMap {
-    fn new(input: &str) -> Self {
-        DiskMap {
-            compact_map: input
-                .chars()
-                .map(|ch| ch.to_digit(10).unwrap() as u8)
-                .collect(),
+    fn new(input: &str) -> Result<Self> {
+        if input.is_empty() {
+            bail!(
+                help = "The disk map is a line of digits, alternating file and free space lengths.",
+                "The disk map is empty."
+            );
+        }
+
+        let span = parse::input(input);
+        let compact_map = input
+            .char_indices()
+            .map(|(i, ch)| {
+                let digit = span.slice(i..i + ch.len_utf8());
+                match ch.to_digit(10) {
+                    // the lengths of files and free spaces alternate, starting with a file
+                    Some(0) if i % 2 == 0 => Err(digit
+                        .error(format!("file {} has no blocks", i / 2))
+                        .with_help("Every file has a length from 1 to 9.")),
+                    Some(n) => Ok(n as u8),
+                    None => Err(digit
+                        .error(format!("unexpected character {:?} in disk map", ch))
+                        .with_help("The disk map consists of digits only.")),
+                }
+            })
+            .collect::<ParseResult<Vec<u8>>>()?;
+
+        Ok(DiskMap {
+            compact_map,
             block_map: vec![],
             max_file_id: -1,
-        }
+        })
     }
 
     fn create_block_map(&mut self) {
@@ -68,12 +93,12 @@ impl DiskMap {
 
         while move_to_idx < move_from_idx {
             // find next free slot from left
-            while self.block_map[move_to_idx] != FREE && move_from_idx < self.block_map.len() {
+            while move_to_idx < move_from_idx && self.block_map[move_to_idx] != FREE {
                 move_to_idx += 1;
             }
 
             // find the next file block from right
-            while self.block_map[move_from_idx] == FREE && move_from_idx > 0 {
+            while move_from_idx > move_to_idx && self.block_map[move_from_idx] == FREE {
                 move_from_idx -= 1;
             }
 
@@ -96,13 +121,11 @@ impl DiskMap {
                 Some(free_begin_idx) => {
                     if free_begin_idx < file_begin_idx {
                         // move file if free space ist found on the left side
-                        let mut free_idx = free_begin_idx;
-                        for i in file_begin_idx..=file_end_idx {
+                        for (free_idx, i) in (free_begin_idx..).zip(file_begin_idx..=file_end_idx) {
                             assert_eq!(self.block_map[free_idx], FREE);
                             assert_eq!(self.block_map[i], file_id);
                             self.block_map[free_idx] = self.block_map[i];
                             self.block_map[i] = FREE;
-                            free_idx += 1;
                         }
                     }
                 }
@@ -175,31 +198,50 @@ impl DiskMap {
     }
 }
 
+const EXAMPLE: &str = "2333133121414131402";
+
+const EXAMPLES: &[Example] = &[Example::new("example", EXAMPLE).part1("1928").part2("2858")];
+
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    fn example() -> &'static str {
-        "2333133121414131402"
-    }
+    use crate::answers::assert_input_answer;
+    use crate::util::{assert_example_answers, Part};
 
     #[test]
     fn test_part1_with_examples() {
-        assert_eq!(solve_part1(example()), "1928");
+        assert_example_answers(&get_day(), Part::One);
     }
 
     #[test]
     fn test_part1_with_input() {
-        assert_eq!(solve_part1(&get_day().read_input()), "6341711060162");
+        assert_input_answer(&get_day(), Part::One);
     }
 
     #[test]
     fn test_part2_with_examples() {
-        assert_eq!(solve_part2(example()), "2858");
+        assert_example_answers(&get_day(), Part::Two);
     }
 
     #[test]
     fn test_part2_with_input() {
-        assert_eq!(solve_part2(&get_day().read_input()), "6377400869326");
+        assert_input_answer(&get_day(), Part::Two);
+    }
+
+    #[test]
+    fn test_invalid_disk_map() {
+        assert_eq!(
+            DiskMap::new("").unwrap_err().to_string(),
+            "The disk map is empty."
+        );
+        assert_eq!(
+            DiskMap::new("1203").unwrap_err().to_string(),
+            "line 1, column 3: file 1 has no blocks"
+        );
+        // no free space to compact into
+        assert_eq!(
+            solve_part1("102", &Params::default()).unwrap(),
+            Answer::Int(3)
+        );
     }
 }
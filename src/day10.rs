@@ -1,195 +1,202 @@
-use std::collections::HashMap;
+use std::collections::HashSet;
 
-use crate::util::Day;
+use log::debug;
+use miette::{bail, Result};
+
+use crate::util::{Answer, Day, Example, Grid, Params, Point, Solution};
 
 const DAY_NR: u8 = 10;
 const PROBLEM_TITLE: &str = "Hoof It";
 
 pub fn get_day() -> Day {
-    Day::new(DAY_NR, PROBLEM_TITLE, solve_part1, solve_part2)
+    Day::from_solution(DAY_NR, PROBLEM_TITLE, HoofIt).with_examples(EXAMPLES)
 }
 
-fn solve_part1(input: &str) -> String {
-    let mut topo = TopographicMap::new(input);
-    topo.find_all_trails();
+const TRAIL_END: u8 = 9;
 
-    println!("{:?}", topo);
+struct HoofIt;
 
-    "36".to_string()
-}
+impl Solution for HoofIt {
+    type Parsed = TopographicMap;
 
-fn solve_part2(_input: &str) -> String {
-    "42".to_string()
-}
-
-#[derive(Debug, Eq, Hash, PartialEq, Copy, Clone)]
-struct Pos {
-    row: usize,
-    col: usize,
-}
+    fn parse(&self, input: &str) -> Result<TopographicMap> {
+        TopographicMap::new(input)
+    }
 
-impl Pos {
-    fn new(row: usize, col: usize) -> Self {
-        Pos { row, col }
+    fn part1(&self, topo: &mut TopographicMap, _params: &Params) -> Result<Answer> {
+        Ok(topo
+            .trail_ends()
+            .iter()
+            .map(|ends| score(ends))
+            .sum::<usize>()
+            .into())
     }
-}
 
-#[derive(Debug, Clone)]
-struct Trail {
-    positions: Vec<(Pos, u8)>,
+    fn part2(&self, topo: &mut TopographicMap, _params: &Params) -> Result<Answer> {
+        Ok(topo.trail_ends().iter().map(Vec::len).sum::<usize>().into())
+    }
 }
 
-impl Trail {
-    fn new(head: Pos, height: u8) -> Trail {
-        assert_eq!(height, 0);
-        let positions = vec![(head, height)];
-        Trail { positions }
-    }
+// the number of distinct trail ends reachable from a trailhead
+fn score(ends: &[Point]) -> usize {
+    ends.iter().collect::<HashSet<_>>().len()
 }
 
 #[derive(Debug)]
 struct TopographicMap {
-    heights: HashMap<Pos, u8>,
-    rows: usize,
-    cols: usize,
-    trail_heads: HashMap<Pos, Vec<Trail>>,
+    // None for impassable tiles
+    heights: Grid<Option<u8>>,
+    // the end of every trail per trailhead, found by the first part that needs them, a trail
+    // end is listed once for each trail that leads to it
+    trail_ends: Option<Vec<Vec<Point>>>,
 }
 
 impl TopographicMap {
-    fn new(input: &str) -> Self {
-        let mut heights = HashMap::<Pos, u8>::new();
-
-        let mut rows = 0;
-        let mut cols = 0;
-        for (row, line) in input.lines().enumerate() {
-            for (col, ch) in line.chars().enumerate() {
-                heights.insert(Pos::new(row, col), ch.to_digit(10).unwrap() as u8);
-                cols = col;
-            }
-            rows = row;
-        }
-
-        // TopographicMap {
-        //     heights: input
-        //         .lines()
-        //         .map(|line| {
-        //             line.chars()
-        //                 .map(|ch| ch.to_digit(10).unwrap() as u8)
-        //                 .collect::<Vec<u8>>()
-        //         })
-        //         .collect(),
-        //     trails: vec![],
-        // }
-
-        TopographicMap {
+    fn new(input: &str) -> Result<Self> {
+        let heights = Grid::try_parse(input, |ch| match ch {
+            '.' => Ok(None),
+            '0'..='9' => Ok(Some(ch as u8 - b'0')),
+            _ => bail!(
+                help = "The map consists of heights from 0 to 9 and '.' for impassable tiles.",
+                "unexpected character {:?} in map",
+                ch
+            ),
+        })?;
+
+        Ok(TopographicMap {
             heights,
-            rows,
-            cols,
-            trail_heads: HashMap::new(),
-        }
+            trail_ends: None,
+        })
     }
 
-    fn find_all_trails(&mut self) {
-        self.trail_heads = HashMap::new();
-
-        // start by finding all zero height positions and create (empty) trail heads
-        for (pos, height) in &self.heights {
-            if *height == 0 {
-                self.trail_heads
-                    .insert(*pos, vec![Trail::new(*pos, *height)]);
-            }
-        }
-
-        for (_, trail_head) in &mut self.trail_heads {
-            let mut completed = false;
-            while !completed {
-                completed = true;
-                for (_idx, trail) in trail_head.into_iter().enumerate() {
-                    let last_pos = trail.positions[trail.positions.len() - 1];
+    fn trail_ends(&mut self) -> &[Vec<Point>] {
+        let heights = &self.heights;
+        self.trail_ends
+            .get_or_insert_with(|| find_all_trail_ends(heights))
+    }
+}
 
-                    if last_pos.1 == 9 {
-                        continue;
+// The trail ends per trailhead, a trail climbs by one at every step from 0 to 9. The search
+// only follows the end of each trail, every branch adds a trail.
+fn find_all_trail_ends(heights: &Grid<Option<u8>>) -> Vec<Vec<Point>> {
+    let heads = heights.find_all(|&height| height == Some(0));
+    let trail_ends = heads
+        .map(|head| {
+            let mut ends = vec![];
+            let mut open = vec![head];
+            while let Some(pos) = open.pop() {
+                let next_positions = find_next_pos(heights, pos);
+                if next_positions.is_empty() {
+                    if heights[pos] == Some(TRAIL_END) {
+                        ends.push(pos);
                     }
-
-                    // let x = self.find_next_pos(last_pos.0);
-
-                    // if x.len() == 0 {
-                    //     trail_head.remove(idx);
-                    //     break;
-                    // }
-
-                    // if x.len() >= 1 {
-                    //     &trail
-                    //         .positions
-                    //         .push((x[0], *self.heights.get(&x[0]).unwrap()));
-                    // }
+                    continue;
                 }
+                open.extend(next_positions);
             }
-        }
-    }
+            ends
+        })
+        .collect::<Vec<_>>();
+    debug!(
+        "{} trailheads with {} trails",
+        trail_ends.len(),
+        trail_ends.iter().map(Vec::len).sum::<usize>()
+    );
+
+    trail_ends
+}
 
-    fn find_next_pos(&self, pos: Pos) -> Vec<Pos> {
-        let directions: Vec<Vec<i32>> = vec![vec![0, -1], vec![-1, 0], vec![0, 1], vec![1, 0]];
-        let mut result = vec![];
-        let height = self.heights.get(&pos).unwrap();
-
-        for dir in directions {
-            let row_delta = dir[0];
-            let col_delta = dir[1];
-
-            if pos.row == 0 && row_delta < 0
-                || pos.row == self.rows - 1 && row_delta > 0
-                || pos.col == 0 && col_delta < 0
-                || pos.col == self.cols - 1 && col_delta > 0
-            {
-                continue;
-            }
+fn find_next_pos(heights: &Grid<Option<u8>>, pos: Point) -> Vec<Point> {
+    let Some(height) = heights[pos] else {
+        return vec![];
+    };
 
-            let next_pos = Pos::new(
-                (pos.row as i32 + row_delta) as usize,
-                (pos.col as i32 + col_delta) as usize,
-            );
-            if self.heights.get(&next_pos).unwrap() > height {
-                result.push(next_pos);
-            }
-        }
-
-        result
-    }
+    heights
+        .neighbours4(pos)
+        .filter(|&next_pos| heights[next_pos] == Some(height + 1))
+        .collect()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    fn example() -> &'static str {
-        "89010123
+const EXAMPLE_FORK: &str = "...0...
+...1...
+...2...
+6543456
+7.....7
+8.....8
+9.....9";
+
+const EXAMPLE_FOUR: &str = "..90..9
+...1.98
+...2..7
+6543456
+765.987
+876....
+987....";
+
+const EXAMPLE_TWO_HEADS: &str = "10..9..
+2...8..
+3...7..
+4567654
+...8..3
+...9..2
+.....01";
+
+const EXAMPLE_THREE_TRAILS: &str = ".....0.
+..4321.
+..5..2.
+..6543.
+..7..4.
+..8765.
+..9....";
+
+const EXAMPLE_MANY_TRAILS: &str = "012345
+123456
+234567
+345678
+4.6789
+56789.";
+
+const EXAMPLE: &str = "89010123
 78121874
 87430965
 96549874
 45678903
 32019012
 01329801
-10456732"
-    }
+10456732";
+
+const EXAMPLES: &[Example] = &[
+    Example::new("example", EXAMPLE).part1("36").part2("81"),
+    Example::new("fork", EXAMPLE_FORK).part1("2"),
+    Example::new("four", EXAMPLE_FOUR).part1("4").part2("13"),
+    Example::new("two-heads", EXAMPLE_TWO_HEADS).part1("3"),
+    Example::new("three-trails", EXAMPLE_THREE_TRAILS).part2("3"),
+    Example::new("many-trails", EXAMPLE_MANY_TRAILS).part2("227"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answers::assert_input_answer;
+    use crate::util::{assert_example_answers, Part};
 
     #[test]
     fn test_part1_with_examples() {
-        assert_eq!(solve_part1(example()), "36");
+        assert_example_answers(&get_day(), Part::One);
     }
 
     #[test]
     fn test_part1_with_input() {
-        assert_eq!(solve_part1(&get_day().read_input()), "17");
+        assert_input_answer(&get_day(), Part::One);
     }
 
     #[test]
     fn test_part2_with_examples() {
-        assert_eq!(solve_part2(example()), "42");
+        assert_example_answers(&get_day(), Part::Two);
     }
 
     #[test]
     fn test_part2_with_input() {
-        assert_eq!(solve_part2(&get_day().read_input()), "42");
+        assert_input_answer(&get_day(), Part::Two);
     }
 }
@@ -1,26 +1,33 @@
 use std::collections::HashMap;
 
-use crate::util::Day;
+use miette::Result;
+
+use crate::util::parse;
+use crate::util::{Answer, Day, Example, Param, Params};
 
 const DAY_NR: u8 = 11;
 const PROBLEM_TITLE: &str = "Plutonian Pebbles";
 
+const PARAMS: &[Param] = &[Param::new("blinks", 25).part2(75)];
+
 pub fn get_day() -> Day {
     Day::new(DAY_NR, PROBLEM_TITLE, solve_part1, solve_part2)
+        .with_examples(EXAMPLES)
+        .with_params(PARAMS)
 }
 
-fn solve_part1(input: &str) -> String {
-    blink(input, 25).to_string()
+fn solve_part1(input: &str, params: &Params) -> Result<Answer> {
+    Ok(blink(input, params.get("blinks")?)?.into())
 }
 
-fn solve_part2(input: &str) -> String {
-    blink(input, 75).to_string()
+fn solve_part2(input: &str, params: &Params) -> Result<Answer> {
+    Ok(blink(input, params.get("blinks")?)?.into())
 }
 
-fn blink(input: &str, blinks: u32) -> u64 {
+fn blink(input: &str, blinks: u32) -> Result<u64> {
     let mut stones: HashMap<u64, u64> = HashMap::new();
 
-    for stone in input.split_whitespace().map(|s| s.parse::<u64>().unwrap()) {
+    for stone in parse::input(input).words::<u64>()? {
         stones.insert(stone, 1u64);
     }
 
@@ -31,7 +38,7 @@ fn blink(input: &str, blinks: u32) -> u64 {
                 add(&mut new_stones, 1, number);
             } else {
                 let digits = digits(stone);
-                if digits % 2 == 0 {
+                if digits.is_multiple_of(2) {
                     let pow = 10u64.pow(digits / 2);
                     add(&mut new_stones, stone / pow, number);
                     add(&mut new_stones, stone % pow, number);
@@ -48,7 +55,7 @@ fn blink(input: &str, blinks: u32) -> u64 {
         count += number;
     }
 
-    count
+    Ok(count)
 }
 
 fn add(stones: &mut HashMap<u64, u64>, stone: u64, number: u64) {
@@ -73,31 +80,43 @@ fn digits(n: u64) -> u32 {
     count
 }
 
+const EXAMPLE: &str = "125 17";
+
+const EXAMPLES: &[Example] = &[Example::new("example", EXAMPLE)
+    .part1("55312")
+    .part2("65601038650482")];
+
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    fn example() -> &'static str {
-        "125 17"
-    }
+    use crate::answers::assert_input_answer;
+    use crate::util::{assert_example_answers, Part};
 
     #[test]
     fn test_part1_with_examples() {
-        assert_eq!(solve_part1(example()), "55312");
+        assert_example_answers(&get_day(), Part::One);
     }
 
     #[test]
     fn test_part1_with_input() {
-        assert_eq!(solve_part1(&get_day().read_input()), "211306");
+        assert_input_answer(&get_day(), Part::One);
     }
 
     #[test]
     fn test_part2_with_examples() {
-        assert_eq!(solve_part2(example()), "65601038650482");
+        assert_example_answers(&get_day(), Part::Two);
     }
 
     #[test]
     fn test_part2_with_input() {
-        assert_eq!(solve_part2(&get_day().read_input()), "250783680217283");
+        assert_input_answer(&get_day(), Part::Two);
+    }
+
+    #[test]
+    fn test_invalid_stone() {
+        let error = blink("125 1x7", 1).unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("line 1, column 5: invalid value '1x7'"));
     }
 }
@@ -1,16 +1,21 @@
-use regex::Regex;
+use miette::Result;
 
-use crate::util::Day;
+use crate::util::{parse, Answer, Day, Example, Param, Params};
 
 const DAY_NR: u8 = 13;
 const PROBLEM_TITLE: &str = "Claw Contraption";
 
+// added to the prize positions
+const PARAMS: &[Param] = &[Param::new("offset", 0).part2(10000000000000)];
+
 pub fn get_day() -> Day {
     Day::new(DAY_NR, PROBLEM_TITLE, solve_part1, solve_part2)
+        .with_examples(EXAMPLES)
+        .with_params(PARAMS)
 }
 
-fn solve_part1(input: &str) -> String {
-    let machines = parse_input(input, true);
+fn solve_part1(input: &str, params: &Params) -> Result<Answer> {
+    let machines = parse_input(input, params.get("offset")?)?;
 
     let mut token: u32 = 0;
 
@@ -21,11 +26,11 @@ fn solve_part1(input: &str) -> String {
         }
     }
 
-    token.to_string()
+    Ok(token.into())
 }
 
-fn solve_part2(input: &str) -> String {
-    let machines = parse_input(input, false);
+fn solve_part2(input: &str, params: &Params) -> Result<Answer> {
+    let machines = parse_input(input, params.get("offset")?)?;
 
     let mut token: i128 = 0;
 
@@ -36,65 +41,38 @@ fn solve_part2(input: &str) -> String {
         }
     }
 
-    token.to_string()
+    Ok(token.into())
 }
 
-fn parse_input(input: &str, is_part1: bool) -> Vec<ClawMachine> {
+fn parse_input(input: &str, offset: i128) -> Result<Vec<ClawMachine>> {
     let mut machines = vec![];
 
     // Button A: X+26, Y+66
-    let button_re = Regex::new(r"Button [AB]: X\+(\d+), Y\+(\d+)").unwrap();
-    // Prize: X=18641, Y=10279"
-    let prize_re = Regex::new(r"Prize: X=(\d+), Y=(\d+)").unwrap();
-    for machine_str in input.split("\n\n") {
-        let mut a1: i128 = 0;
-        let mut a2: i128 = 0;
-        let mut b1: i128 = 0;
-        let mut b2: i128 = 0;
-        let mut c1: i128 = 0;
-        let mut c2: i128 = 0;
-
-        let mut l = machine_str.lines();
-
-        for (_, [x, y]) in button_re
-            .captures_iter(l.next().unwrap())
-            .map(|c| c.extract())
-        {
-            a1 = x.parse::<i128>().unwrap();
-            a2 = y.parse::<i128>().unwrap();
-        }
-
-        for (_, [x, y]) in button_re
-            .captures_iter(l.next().unwrap())
-            .map(|c| c.extract())
-        {
-            b1 = x.parse::<i128>().unwrap();
-            b2 = y.parse::<i128>().unwrap();
-        }
-
-        for (_, [x, y]) in prize_re
-            .captures_iter(l.next().unwrap())
-            .map(|c| c.extract())
-        {
-            c1 = x.parse::<i128>().unwrap();
-            c2 = y.parse::<i128>().unwrap();
-            if !is_part1 {
-                c1 += 10000000000000;
-                c2 += 10000000000000;
-            }
-        }
+    // Button B: X+67, Y+21
+    // Prize: X=12748, Y=12176
+    for machine in parse::sections(input) {
+        let mut lines = machine.lines();
+        let mut next_line = || {
+            lines
+                .next()
+                .ok_or_else(|| machine.error("incomplete claw machine"))
+        };
+
+        let [a1, a2] = next_line()?.ints_n()?;
+        let [b1, b2] = next_line()?.ints_n()?;
+        let [c1, c2] = next_line()?.ints_n::<i128, 2>()?;
 
         machines.push(ClawMachine {
             a1,
             a2,
             b1,
             b2,
-            c1,
-            c2,
+            c1: c1 + offset,
+            c2: c2 + offset,
         });
     }
 
-    machines
+    Ok(machines)
 }
 
 #[derive(Debug)]
@@ -121,12 +99,7 @@ impl ClawMachine {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    fn example() -> &'static str {
-        "Button A: X+94, Y+34
+const EXAMPLE: &str = "Button A: X+94, Y+34
 Button B: X+22, Y+67
 Prize: X=8400, Y=5400
 
@@ -140,26 +113,35 @@ Prize: X=7870, Y=6450
 
 Button A: X+69, Y+23
 Button B: X+27, Y+71
-Prize: X=18641, Y=10279"
-    }
+Prize: X=18641, Y=10279";
+
+const EXAMPLES: &[Example] = &[Example::new("example", EXAMPLE)
+    .part1("480")
+    .part2("875318608908")];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answers::assert_input_answer;
+    use crate::util::{assert_example_answers, Part};
 
     #[test]
     fn test_part1_with_examples() {
-        assert_eq!(solve_part1(example()), "480");
+        assert_example_answers(&get_day(), Part::One);
     }
 
     #[test]
     fn test_part1_with_input() {
-        assert_eq!(solve_part1(&get_day().read_input()), "28138");
+        assert_input_answer(&get_day(), Part::One);
     }
 
     #[test]
     fn test_part2_with_examples() {
-        assert_eq!(solve_part2(example()), "875318608908");
+        assert_example_answers(&get_day(), Part::Two);
     }
 
     #[test]
     fn test_part2_with_input() {
-        assert_eq!(solve_part2(&get_day().read_input()), "108394825772874");
+        assert_input_answer(&get_day(), Part::Two);
     }
 }
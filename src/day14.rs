@@ -1,84 +1,86 @@
-use glam::IVec2;
 use itertools::Itertools;
-use regex::Regex;
+use miette::{bail, Result};
 
-use crate::util::Day;
+use crate::util::parse::{self, ParseResult, Span};
+use crate::util::render::{Canvas, Render};
+use crate::util::{export, record};
+use crate::util::{Answer, Day, Example, Grid, Param, Params, Point};
 
 const DAY_NR: u8 = 14;
 const PROBLEM_TITLE: &str = "Restroom Redoubt";
 
+const PARAMS: &[Param] = &[
+    Param::new("width", 101),
+    Param::new("height", 103),
+    Param::new("seconds", 100),
+];
+
 pub fn get_day() -> Day {
     Day::new(DAY_NR, PROBLEM_TITLE, solve_part1, solve_part2)
+        .with_examples(EXAMPLES)
+        .with_params(PARAMS)
 }
 
-fn solve_part1(input: &str) -> String {
-    solve_part1_helper(input, 101, 103)
-}
-
-fn solve_part1_helper(input: &str, width: i32, height: i32) -> String {
-    let mut floor = BathroomFloor::new(width, height, input);
-    floor.move_robots(100);
-    floor.safety_factor().to_string()
+fn solve_part1(input: &str, params: &Params) -> Result<Answer> {
+    let mut floor = bathroom_floor(input, params)?;
+    let seconds: u32 = params.get("seconds")?;
+    floor.move_robots(seconds);
+    export::image(&format!("second-{}", seconds), &floor, &[]);
+    Ok(floor.safety_factor().into())
 }
 
-fn solve_part2(input: &str) -> String {
+fn solve_part2(input: &str, params: &Params) -> Result<Answer> {
     // Honestly I don't know why this is working - I didn't even understand the the task completely.
     // I copied the soltution from Felix: https://github.com/Gronner
 
-    let mut floor = BathroomFloor::new(101, 103, input);
+    let mut floor = bathroom_floor(input, params)?;
     let nr_robots = floor.robots.len();
-    let mut seconds = 0;
-    loop {
-        seconds += 1;
+    // the robots are back at their start after width * height seconds
+    let period = floor.width as u32 * floor.height as u32;
+    for seconds in 1..=period {
         floor.move_robots(1);
         if floor.robots.iter().map(|robot| robot.pos).unique().count() == nr_robots {
-            break;
+            export::image(&format!("second-{}", seconds), &floor, &[]);
+            return Ok(seconds.into());
         }
     }
-    seconds.to_string()
+    bail!(
+        "The robots never stand on different tiles within {} seconds.",
+        period
+    )
+}
+
+fn bathroom_floor(input: &str, params: &Params) -> Result<BathroomFloor> {
+    BathroomFloor::new(
+        params.get_positive("width")?,
+        params.get_positive("height")?,
+        input,
+    )
 }
 
 #[derive(Debug)]
 struct Robot {
-    pos: IVec2,
-    v: IVec2,
+    pos: Point,
+    v: Point,
 }
 
 impl Robot {
-    fn new(input: &str) -> Self {
+    fn new(line: Span) -> ParseResult<Self> {
         // p=2,0 v=2,-1
-        let re = Regex::new(r"p=(?<x>\d+),(?<y>\d+) v=(?<vx>-?\d+),(?<vy>-?\d+)").unwrap();
-        let mut pos = IVec2::ZERO;
-        let mut v = IVec2::ZERO;
-        for (_, [x, y, v_x, v_y]) in re.captures_iter(input).map(|c| c.extract()) {
-            pos.x = x.parse::<i32>().unwrap();
-            pos.y = y.parse::<i32>().unwrap();
-            v.x = v_x.parse::<i32>().unwrap();
-            v.y = v_y.parse::<i32>().unwrap();
-        }
+        let [x, y, v_x, v_y] = line.ints_n()?;
+        let pos = Point::new(x, y);
+        let v = Point::new(v_x, v_y);
 
-        Robot { pos, v }
+        Ok(Robot { pos, v })
     }
 
+    // the robots teleport to the other side at the edges of the floor
     fn mov(&mut self, width: i32, height: i32) {
-        self.pos.x += self.v.x;
-        while self.pos.x < 0 {
-            self.pos.x += width;
-        }
-        while self.pos.x > width - 1 {
-            self.pos.x -= width;
-        }
-
-        self.pos.y += self.v.y;
-        while self.pos.y < 0 {
-            self.pos.y += height;
-        }
-        while self.pos.y > height - 1 {
-            self.pos.y -= height;
-        }
+        self.pos.x = (self.pos.x + self.v.x).rem_euclid(width);
+        self.pos.y = (self.pos.y + self.v.y).rem_euclid(height);
     }
 
-    fn is_in(&self, quad: (IVec2, IVec2)) -> bool {
+    fn is_in(&self, quad: (Point, Point)) -> bool {
         //dbg!(self, quad);
         self.pos.x >= quad.0.x
             && self.pos.x <= quad.0.y
@@ -95,42 +97,38 @@ struct BathroomFloor {
 }
 
 impl BathroomFloor {
-    fn new(width: i32, height: i32, input: &str) -> Self {
-        BathroomFloor {
+    fn new(width: i32, height: i32, input: &str) -> Result<Self> {
+        Ok(BathroomFloor {
             width,
             height,
 
-            robots: input
-                .lines()
-                .map(|line| Robot::new(line))
-                .collect::<Vec<Robot>>(),
-        }
+            robots: parse::lines(input)
+                .map(Robot::new)
+                .collect::<ParseResult<Vec<Robot>>>()?,
+        })
     }
 
     fn move_robots(&mut self, times: u32) {
         for _ in 0..times {
             for robot in &mut self.robots {
-                assert!(robot.pos.x < self.width);
-                assert!(robot.pos.y < self.height);
                 robot.mov(self.width, self.height);
-                assert!(robot.pos.x < self.width);
-                assert!(robot.pos.y < self.height);
             }
+            record::frame(self);
         }
     }
 
     fn safety_factor(&self) -> u32 {
-        let x1 = IVec2::new(0, self.width / 2 - 1);
-        let x2 = IVec2::new(self.width / 2 + 1, self.width + 1);
-        let y1 = IVec2::new(0, self.height / 2 - 1);
-        let y2 = IVec2::new(self.height / 2 + 1, self.height + 1);
+        let x1 = Point::new(0, self.width / 2 - 1);
+        let x2 = Point::new(self.width / 2 + 1, self.width + 1);
+        let y1 = Point::new(0, self.height / 2 - 1);
+        let y2 = Point::new(self.height / 2 + 1, self.height + 1);
 
-        let quads = vec![(x1, y1), (x2, y1), (x1, y2), (x2, y2)];
+        let quads = [(x1, y1), (x2, y1), (x1, y2), (x2, y2)];
 
-        let mut count = vec![0; 4];
+        let mut count = [0; 4];
 
         for robot in &self.robots {
-            for (i, quad) in quads.clone().into_iter().enumerate() {
+            for (i, quad) in quads.into_iter().enumerate() {
                 if robot.is_in(quad) {
                     count[i] += 1;
                 }
@@ -140,12 +138,22 @@ impl BathroomFloor {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+// the number of robots on every tile like in the puzzle description
+impl Render for BathroomFloor {
+    fn canvas(&self) -> Canvas {
+        let mut counts = Grid::new(self.width as usize, self.height as usize, 0);
+        for robot in &self.robots {
+            counts[robot.pos] += 1;
+        }
+        Canvas::new(&counts, |&count| match count {
+            0 => '.',
+            1..=9 => char::from_digit(count, 10).unwrap(),
+            _ => '+',
+        })
+    }
+}
 
-    fn example() -> &'static str {
-        "p=0,4 v=3,-3
+const EXAMPLE: &str = "p=0,4 v=3,-3
 p=6,3 v=-1,-3
 p=10,3 v=-1,2
 p=2,0 v=2,-1
@@ -156,21 +164,30 @@ p=3,0 v=-1,-2
 p=9,3 v=2,3
 p=7,3 v=-1,2
 p=2,4 v=2,-3
-p=9,5 v=-3,-3"
-    }
+p=9,5 v=-3,-3";
+
+const EXAMPLES: &[Example] = &[Example::new("example", EXAMPLE)
+    .part1("12")
+    .params(&[("width", 11), ("height", 7)])];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answers::assert_input_answer;
+    use crate::util::{assert_example_answers, Part};
 
     #[test]
     fn test_part1_with_examples() {
-        assert_eq!(solve_part1_helper(example(), 11, 7), "12");
+        assert_example_answers(&get_day(), Part::One);
     }
 
     #[test]
     fn test_part1_with_input() {
-        assert_eq!(solve_part1(&get_day().read_input()), "232589280");
+        assert_input_answer(&get_day(), Part::One);
     }
 
     #[test]
     fn test_part2_with_input() {
-        assert_eq!(solve_part2(&get_day().read_input()), "7569");
+        assert_input_answer(&get_day(), Part::Two);
     }
 }
@@ -1,28 +1,33 @@
-use glam::{IVec2, UVec2};
+use log::{debug, trace};
+use miette::{bail, miette, Result};
 
-use crate::util::Day;
+use crate::util::record;
+use crate::util::render::{self, Canvas, Render};
+use crate::util::{parse, Answer, Day, Direction, Example, Grid, Params, Point};
 
 const DAY_NR: u8 = 15;
 const PROBLEM_TITLE: &str = "Warehouse Woes";
 
 pub fn get_day() -> Day {
-    Day::new(DAY_NR, PROBLEM_TITLE, solve_part1, solve_part2)
+    Day::new(DAY_NR, PROBLEM_TITLE, solve_part1, solve_part2).with_examples(EXAMPLES)
 }
 
-fn solve_part1(input: &str) -> String {
-    let mut warehouse = Warehouse::new(false, input);
-    println!("{:?}", warehouse);
-    warehouse.print_map();
+fn solve_part1(input: &str, _params: &Params) -> Result<Answer> {
+    let mut warehouse = Warehouse::new(false, input)?;
+    debug!("{:?}", warehouse);
     warehouse.exec_moves();
+    render::show("Warehouse after all moves", &warehouse);
 
-    warehouse.gps_sum().to_string()
+    Ok(warehouse.gps_sum().into())
 }
 
-fn solve_part2(input: &str) -> String {
-    let mut warehouse = Warehouse::new(true, input);
-    warehouse.print_map();
+fn solve_part2(input: &str, _params: &Params) -> Result<Answer> {
+    let mut warehouse = Warehouse::new(true, input)?;
+    debug!("{:?}", warehouse);
+    warehouse.exec_moves();
+    render::show("Wide warehouse after all moves", &warehouse);
 
-    "42".to_string()
+    Ok(warehouse.gps_sum().into())
 }
 
 const WALL: char = '#';
@@ -32,129 +37,84 @@ const BOX_CLOSE: char = ']';
 const ROBOT: char = '@';
 const EMPTY: char = '.';
 
-const UP: IVec2 = IVec2 { x: 0, y: -1 };
-const DOWN: IVec2 = IVec2 { x: 0, y: 1 };
-const LEFT: IVec2 = IVec2 { x: -1, y: 0 };
-const RIGHT: IVec2 = IVec2 { x: 1, y: 0 };
-
 #[derive(Debug)]
 struct Warehouse {
     large_boxes: bool,
-    map: Vec<Vec<char>>,
-    width: usize,
-    height: usize,
-    robot_pos: UVec2,
-    moves: Vec<IVec2>,
+    map: Grid<char>,
+    robot_pos: Point,
+    moves: Vec<Direction>,
 }
 
 impl Warehouse {
-    fn new(large_boxes: bool, input: &str) -> Self {
-        let parts = input.split("\n\n").collect::<Vec<&str>>();
-        assert_eq!(parts.len(), 2);
+    fn new(large_boxes: bool, input: &str) -> Result<Self> {
+        let sections = parse::sections(input);
+        let [map_section, moves_section] = sections[..] else {
+            bail!(
+                help = "The map comes first, then a blank line and the moves.",
+                "Expected the map and the moves separated by a blank line."
+            );
+        };
 
         // read warehouse map
-        let mut map = vec![];
-        for line in parts[0].lines() {
-            let mut map_line: Vec<char> = vec![];
-            for ch in line.chars() {
-                match ch {
-                    WALL => {
-                        map_line.push(WALL);
-                        if large_boxes {
-                            map_line.push(WALL);
-                        }
-                    }
-                    BOX => {
-                        if large_boxes {
-                            map_line.push(BOX_OPEN);
-                            map_line.push(BOX_CLOSE);
-                        } else {
-                            map_line.push(BOX);
-                        }
-                    }
-                    EMPTY => {
-                        map_line.push(EMPTY);
-                        if large_boxes {
-                            map_line.push(EMPTY);
-                        }
-                    }
-                    ROBOT => {
-                        map_line.push(ROBOT);
-                        if large_boxes {
-                            map_line.push(EMPTY);
-                        }
-                    }
-                    _ => panic!("Unexpected map character."),
-                }
-            }
-            map.push(map_line);
-        }
+        let map = Grid::try_parse(map_section, |ch| match ch {
+            WALL | BOX | EMPTY | ROBOT => Ok(ch),
+            _ => bail!(
+                help = format!("The map consists of {WALL:?}, {BOX:?}, {EMPTY:?} and {ROBOT:?}."),
+                "unexpected map character {:?}",
+                ch
+            ),
+        })?;
+        let map = match large_boxes {
+            true => widen(&map),
+            false => map,
+        };
 
-        let height = map.len();
-        let width = map[0].len();
-
-        // check map and find robot
-        let mut robot_pos: Option<UVec2> = None;
-        for y in 0..height {
-            for x in 0..width {
-                let tile = map[y][x];
-                if tile == ROBOT {
-                    robot_pos = Some(UVec2 {
-                        x: x as u32,
-                        y: y as u32,
-                    })
-                }
-            }
-        }
-        assert_ne!(robot_pos, None);
+        let robot_pos = map.find(|&tile| tile == ROBOT).ok_or_else(|| {
+            miette!(
+                help = format!("Mark the position of the robot with {:?}.", ROBOT),
+                "No robot found in map."
+            )
+        })?;
 
         // read robot moves
-        let mut moves: Vec<IVec2> = vec![];
-        for line in parts[1].lines() {
-            for ch in line.chars() {
-                moves.push(match ch {
-                    '^' => UP,
-                    'v' => DOWN,
-                    '<' => LEFT,
-                    '>' => RIGHT,
-                    _ => panic!("Unexpected move."),
-                });
+        let mut moves: Vec<Direction> = vec![];
+        for line in moves_section.lines() {
+            for (i, ch) in line.as_str().char_indices() {
+                moves.push(Direction::from_arrow(ch).ok_or_else(|| {
+                    line.slice(i..i + ch.len_utf8())
+                        .error(format!("unexpected move {:?}", ch))
+                        .with_help("The moves are '^', '>', 'v' and '<'.")
+                })?);
             }
         }
 
-        Warehouse {
+        Ok(Warehouse {
             large_boxes,
             map,
-            width: width,
-            height: height,
-            robot_pos: robot_pos.unwrap(),
+            robot_pos,
             moves,
-        }
+        })
     }
 
     fn exec_moves(&mut self) {
         for mv in self.moves.clone() {
             self.exec_move(mv);
-            self.print_map();
+            record::frame(self);
+            trace!(
+                "Move {}, robot @ {}:\n{}",
+                mv,
+                self.robot_pos,
+                self.canvas()
+            );
         }
     }
 
-    fn exec_move(&mut self, dir: IVec2) {
-        let new_pos = UVec2 {
-            x: (self.robot_pos.x as i32 + dir.x) as u32,
-            y: (self.robot_pos.y as i32 + dir.y) as u32,
-        };
+    fn exec_move(&mut self, dir: Direction) {
+        let new_pos = self.robot_pos + dir;
 
-        if self.is_wall(new_pos) {
-            return;
-        } else if self.is_box(new_pos) {
-            let mut search_pos = new_pos.clone();
-            while self.is_box(search_pos) {
-                search_pos.x = (search_pos.x as i32 + dir.x) as u32;
-                search_pos.y = (search_pos.y as i32 + dir.y) as u32;
-            }
-            if self.is_empty(search_pos) {
-                self.move_box(new_pos, search_pos);
+        if self.is_box(new_pos) {
+            if let Some(boxes) = self.pushed_boxes(new_pos, dir) {
+                self.move_boxes(&boxes, dir);
             }
         }
 
@@ -163,80 +123,103 @@ impl Warehouse {
         }
     }
 
-    fn move_robot(&mut self, new_pos: UVec2) {
-        assert!(self.is_empty(new_pos));
-        self.map[self.robot_pos.y as usize][self.robot_pos.x as usize] = EMPTY;
-        self.map[new_pos.y as usize][new_pos.x as usize] = ROBOT;
-        self.robot_pos = new_pos;
+    // All box tiles the robot pushes when it moves onto `pos`, None if a wall blocks any of them.
+    // Wide boxes pushed up or down take their other half with them, which can push further boxes
+    // on both sides.
+    fn pushed_boxes(&self, pos: Point, dir: Direction) -> Option<Vec<Point>> {
+        let vertical = matches!(dir, Direction::Up | Direction::Down);
+        let mut boxes = vec![];
+        let mut open = vec![pos];
+        while let Some(pos) = open.pop() {
+            if self.is_wall(pos) {
+                return None;
+            }
+            if !self.is_box(pos) || boxes.contains(&pos) {
+                continue;
+            }
+            boxes.push(pos);
+            open.push(pos + dir);
+            if vertical {
+                match self.get_tile(pos) {
+                    BOX_OPEN => open.push(pos + Direction::Right),
+                    BOX_CLOSE => open.push(pos + Direction::Left),
+                    _ => (),
+                }
+            }
+        }
+        Some(boxes)
+    }
+
+    fn move_boxes(&mut self, boxes: &[Point], dir: Direction) {
+        let tiles = boxes
+            .iter()
+            .map(|&pos| (pos, self.get_tile(pos)))
+            .collect::<Vec<_>>();
+        for &(pos, _) in &tiles {
+            self.map[pos] = EMPTY;
+        }
+        for (pos, tile) in tiles {
+            assert!(self.is_empty(pos + dir));
+            self.map[pos + dir] = tile;
+        }
     }
 
-    fn move_box(&mut self, from: UVec2, to: UVec2) {
-        assert!(self.is_box(from));
-        assert!(self.is_empty(to));
-        self.map[from.y as usize][from.x as usize] = EMPTY;
-        self.map[to.y as usize][to.x as usize] = BOX;
+    fn move_robot(&mut self, new_pos: Point) {
+        assert!(self.is_empty(new_pos));
+        self.map[self.robot_pos] = EMPTY;
+        self.map[new_pos] = ROBOT;
+        self.robot_pos = new_pos;
     }
 
     fn gps_sum(&self) -> u64 {
-        let mut sum: u64 = 0;
-        let mut pos: UVec2 = UVec2::ZERO;
-
-        for y in 0..self.height {
-            for x in 0..self.width {
-                pos.x = x as u32;
-                pos.y = y as u32;
-                if self.is_box_open(pos) {
-                    sum += 100 * y as u64 + x as u64;
-                }
-            }
-        }
-        sum
+        self.map
+            .find_all(|&tile| tile == BOX || tile == BOX_OPEN)
+            .map(|pos| 100 * pos.y as u64 + pos.x as u64)
+            .sum()
     }
 
-    fn get_tile(&self, pos: UVec2) -> char {
-        self.map[pos.y as usize][pos.x as usize]
+    fn get_tile(&self, pos: Point) -> char {
+        self.map[pos]
     }
 
-    fn is_wall(&self, pos: UVec2) -> bool {
+    fn is_wall(&self, pos: Point) -> bool {
         self.get_tile(pos) == WALL
     }
 
-    fn is_box(&self, pos: UVec2) -> bool {
+    fn is_box(&self, pos: Point) -> bool {
         match self.large_boxes {
             true => self.get_tile(pos) == BOX_OPEN || self.get_tile(pos) == BOX_CLOSE,
             false => self.get_tile(pos) == BOX,
         }
     }
 
-    fn is_box_open(&self, pos: UVec2) -> bool {
-        self.get_tile(pos) == BOX || self.get_tile(pos) == BOX_OPEN
-    }
-
-    fn is_empty(&self, pos: UVec2) -> bool {
+    fn is_empty(&self, pos: Point) -> bool {
         self.get_tile(pos) == EMPTY
     }
+}
 
-    fn print_map(&self) {
-        let mut pos: UVec2 = UVec2::ZERO;
-
-        println!("Robot @ {}", self.robot_pos);
-        for y in 0..self.height {
-            for x in 0..self.width {
-                pos.x = x as u32;
-                pos.y = y as u32;
-                print!("{}", self.get_tile(pos));
-            }
-            println!();
-        }
+impl Render for Warehouse {
+    fn canvas(&self) -> Canvas {
+        Canvas::from_chars(&self.map)
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+// Everything except the robot is twice as wide in the second warehouse.
+fn widen(map: &Grid<char>) -> Grid<char> {
+    let mut wide = Grid::new(2 * map.width(), map.height(), EMPTY);
+    for (pos, &tile) in map.iter() {
+        let (left, right) = match tile {
+            BOX => (BOX_OPEN, BOX_CLOSE),
+            ROBOT => (ROBOT, EMPTY),
+            tile => (tile, tile),
+        };
+        wide[Point::new(2 * pos.x, pos.y)] = left;
+        wide[Point::new(2 * pos.x + 1, pos.y)] = right;
+    }
+    wide
+}
 
-    fn example1() -> &'static str {
-        "########
+const EXAMPLE_SMALL: &str = "########
 #..O.O.#
 ##@.O..#
 #...O..#
@@ -245,11 +228,9 @@ mod tests {
 #......#
 ########
 
-<^^>>>vv<v>>v<<"
-    }
+<^^>>>vv<v>>v<<";
 
-    fn example2() -> &'static str {
-        "##########
+const EXAMPLE_LARGER: &str = "##########
 #..O..O.O#
 #......O.#
 #.OO..O.O#
@@ -269,27 +250,66 @@ vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
 >^>>^v>vv>^<<^v<>><<><<v<<v><>v<^vv<<<>^^v^>^^>>><<^v>>v^v><^^>>^<>vv^
 <><^^>^^^<><vvvvv^v<v<<>^v<v>v<<^><<><<><<<^^<<<^<<>><<><^^^>^^<>^>v<>
 ^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>
-v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^"
-    }
+v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^";
+
+const EXAMPLES: &[Example] = &[
+    Example::new("small", EXAMPLE_SMALL).part1("2028"),
+    Example::new("larger", EXAMPLE_LARGER)
+        .part1("10092")
+        .part2("9021"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answers::assert_input_answer;
+    use crate::util::{assert_example_answers, Part};
 
     #[test]
     fn test_part1_with_examples() {
-        assert_eq!(solve_part1(example1()), "2028");
-        assert_eq!(solve_part1(example2()), "10092");
+        assert_example_answers(&get_day(), Part::One);
     }
 
     #[test]
     fn test_part1_with_input() {
-        assert_eq!(solve_part1(&get_day().read_input()), "1499739");
+        assert_input_answer(&get_day(), Part::One);
     }
 
     #[test]
     fn test_part2_with_examples() {
-        assert_eq!(solve_part2(example2()), "9021");
+        assert_example_answers(&get_day(), Part::Two);
     }
 
     #[test]
     fn test_part2_with_input() {
-        assert_eq!(solve_part2(&get_day().read_input()), "42");
+        assert_input_answer(&get_day(), Part::Two);
+    }
+
+    #[test]
+    fn test_wide_boxes() {
+        let map = "#######\n#..#..#\n#.OO..#\n#..O..#\n#...@.#\n#######";
+        let warehouse = |moves| {
+            let mut warehouse = Warehouse::new(true, &format!("{}\n\n{}", map, moves)).unwrap();
+            warehouse.exec_moves();
+            warehouse.canvas().to_string()
+        };
+
+        // the stacked boxes hit the wall above them, so nothing moves
+        assert_eq!(
+            warehouse("<^"),
+            "##############\n##....##....##\n##..[][]....##\n##....[]....##\n##.....@....##\n##############"
+        );
+        // the upper box is pushed by its right half
+        assert_eq!(
+            warehouse("<<<^^"),
+            "##############\n##..[]##....##\n##...@[]....##\n##....[]....##\n##..........##\n##############"
+        );
+    }
+
+    #[test]
+    fn test_unknown_move() {
+        let error = Warehouse::new(false, "####\n#@.#\n####\n\n<>\n^x").unwrap_err();
+        assert_eq!(error.to_string(), "line 6, column 2: unexpected move 'x'");
+        assert!(error.help().is_some());
     }
 }
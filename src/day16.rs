@@ -1,218 +1,197 @@
 use std::collections::{HashMap, HashSet};
+use std::iter;
 
-use glam::IVec2;
-use itertools::partition;
-use owo_colors::OwoColorize;
-use pathfinding::prelude::{astar, astar_bag, astar_bag_collect, dijkstra};
+use itertools::Itertools;
+use pathfinding::prelude::dijkstra_reach;
 
-use crate::util::Day;
+use miette::{bail, miette, Result};
+
+use crate::util::export;
+use crate::util::render::{self, Canvas, Render};
+use crate::util::{Answer, Day, Direction, Example, Grid, Params, Point, Solution};
 
 const DAY_NR: u8 = 16;
 const PROBLEM_TITLE: &str = "Reindeer Maze";
 
 pub fn get_day() -> Day {
-    Day::new(DAY_NR, PROBLEM_TITLE, solve_part1, solve_part2)
+    Day::from_solution(DAY_NR, PROBLEM_TITLE, ReindeerMaze).with_examples(EXAMPLES)
 }
 
-fn solve_part1(input: &str) -> String {
-    let mut maze = Maze::new(input);
-    if cfg!(test) {
-        maze.print(true);
-    }
-    let score = maze.best_path_score();
-    if cfg!(test) {
-        maze.print(true);
-    }
+struct ReindeerMaze;
 
-    score.to_string()
-}
+impl Solution for ReindeerMaze {
+    type Parsed = Maze;
 
-fn solve_part2(input: &str) -> String {
-    let mut maze = Maze::new(input);
-    if cfg!(test) {
-        maze.print(false);
-    }
-    let tiles = maze.nr_best_path_tiles();
-    if cfg!(test) {
-        maze.print(false);
+    fn parse(&self, input: &str) -> Result<Maze> {
+        Maze::new(input)
     }
 
-    tiles.to_string()
-}
-
-// type Pos = IVec2;
+    fn part1(&self, maze: &mut Maze, _params: &Params) -> Result<Answer> {
+        let score = maze.best_path_score()?;
+        render::show("Best path", maze);
+        export::image("best-path", maze, &[positions(&maze.path)]);
 
-// const DIRECTIONS: [Pos; 4] = [
-//     Pos::NEG_Y, // up
-//     Pos::X,     // right
-//     Pos::Y,     // down
-//     Pos::NEG_X, // left
-// ];
+        Ok(score.into())
+    }
 
-// enum Direction {
-//     UP = 0,
-//     RIGHT = 1,
-//     DOWN = 2,
-//     LEFT = 3,
-// }
+    fn part2(&self, maze: &mut Maze, _params: &Params) -> Result<Answer> {
+        let tiles = maze.nr_best_path_tiles()?;
+        render::show("Tiles of the best paths", maze);
+        export::image("best-paths", maze, &[]);
 
-// #[derive(Debug, Eq, Hash)]
-// struct DirectedPos {
-//     pos: Pos,
-//     dir: Direction,
-// }
+        Ok(tiles.into())
+    }
+}
 
 #[derive(Debug)]
 struct Maze {
-    width: usize,
-    height: usize,
-    start: IVec2,
-    end: IVec2,
-    obstacles: HashSet<IVec2>,
-    path: Option<HashMap<IVec2, IVec2>>,
+    tiles: Grid<char>,
+    start: Point,
+    end: Point,
+    // the best score, the costs from the start up to it and the steps of the best path, once
+    // part 1 searched them, part 2 only searches back from the end
+    best_score: Option<usize>,
+    from_start: HashMap<(Point, Direction), usize>,
+    path: Vec<(Point, Direction)>,
+    // the tiles of all best paths, once they are searched
+    best_tiles: HashSet<Point>,
 }
 
 impl Maze {
-    fn new(input: &str) -> Self {
-        let mut width = input.lines().next().unwrap().len();
-        let mut height = 0;
-        let mut start: Option<IVec2> = None;
-        let mut end: Option<IVec2> = None;
-        let mut obstacles: HashSet<IVec2> = HashSet::new();
-
-        for (y, line) in input.lines().enumerate() {
-            for (x, ch) in line.chars().enumerate() {
-                let pos = IVec2 {
-                    x: x as i32,
-                    y: y as i32,
-                };
-                match ch {
-                    '#' => {
-                        obstacles.insert(pos);
-                    }
-                    '.' => (),
-                    'S' => {
-                        start = Some(pos);
-                    }
-                    'E' => {
-                        end = Some(pos);
-                    }
-                    _ => panic!("unexpected maze character {}", ch),
-                };
-            }
+    fn new(input: &str) -> Result<Self> {
+        let tiles = Grid::try_parse(input, |ch| match ch {
+            '#' | '.' | 'S' | 'E' => Ok(ch),
+            _ => bail!(
+                help = "The maze consists of '#', '.', 'S' and 'E'.",
+                "unexpected maze character {:?}",
+                ch
+            ),
+        })?;
+
+        Ok(Maze {
+            start: tiles.find(|&tile| tile == 'S').ok_or_else(|| {
+                miette!(help = "Mark the start tile with 'S'.", "No Start found.")
+            })?,
+            end: tiles
+                .find(|&tile| tile == 'E')
+                .ok_or_else(|| miette!(help = "Mark the end tile with 'E'.", "No End found."))?,
+            tiles,
+            best_score: None,
+            from_start: HashMap::new(),
+            path: vec![],
+            best_tiles: HashSet::new(),
+        })
+    }
 
-            height += 1;
-        }
-        Maze {
-            width,
-            height,
-            obstacles,
-            start: start.expect("No Start found."),
-            end: end.expect("No End found."),
-            path: None,
+    fn is_wall(&self, pos: Point) -> bool {
+        self.tiles.get(pos).is_none_or(|&tile| tile == '#')
+    }
+
+    // The reindeer either steps forward or turns in place.
+    fn successors(&self, pos: Point, dir: Direction) -> Vec<((Point, Direction), usize)> {
+        let turns = [
+            ((pos, dir.turn_right()), 1000),
+            ((pos, dir.turn_left()), 1000),
+        ];
+        let next_pos = pos + dir;
+        match self.is_wall(next_pos) {
+            true => turns.to_vec(),
+            false => [((next_pos, dir), 1)].into_iter().chain(turns).collect(),
         }
     }
 
-    fn best_path_score(&mut self) -> usize {
-        let result = astar(
-            &(self.start, IVec2::X),
-            |(pos, dir)| {
-                let next_pos = pos + dir;
-                if self.obstacles.contains(&next_pos) {
-                    vec![((*pos, dir.perp()), 1000), ((*pos, -dir.perp()), 1000)]
-                } else {
-                    vec![
-                        ((next_pos, *dir), 1),
-                        ((*pos, dir.perp()), 1000),
-                        ((*pos, -dir.perp()), 1000),
-                    ]
-                }
-            },
-            |(_, _)| 0,
-            |&(pos, _)| pos == self.end,
-        );
-
-        self.path = Some(HashMap::from_iter(result.as_ref().unwrap().0.clone()));
-
-        result.unwrap().1
+    // The steps that lead to a state, the reverse of the successors. All end states follow the
+    // state None, so a single search from the end reaches them.
+    fn predecessors(
+        &self,
+        state: Option<(Point, Direction)>,
+    ) -> Vec<(Option<(Point, Direction)>, usize)> {
+        let Some((pos, dir)) = state else {
+            return Direction::ALL
+                .iter()
+                .map(|&dir| (Some((self.end, dir)), 0))
+                .collect();
+        };
+        let turns = [
+            (Some((pos, dir.turn_right())), 1000),
+            (Some((pos, dir.turn_left())), 1000),
+        ];
+        let prev_pos = pos + dir.reverse();
+        match self.is_wall(prev_pos) {
+            true => turns.to_vec(),
+            false => [(Some((prev_pos, dir)), 1)]
+                .into_iter()
+                .chain(turns)
+                .collect(),
+        }
     }
 
-    fn nr_best_path_tiles(&mut self) -> usize {
-        let result = astar_bag(
-            &(self.start, IVec2::X),
-            |(pos, dir)| {
-                let next_pos = pos + dir;
-                if self.obstacles.contains(&next_pos) {
-                    vec![((*pos, dir.perp()), 1000), ((*pos, -dir.perp()), 1000)]
-                } else {
-                    vec![
-                        ((next_pos, *dir), 1),
-                        ((*pos, dir.perp()), 1000),
-                        ((*pos, -dir.perp()), 1000),
-                    ]
-                }
-            },
-            |(_, _)| 0,
-            |&(pos, _)| pos == self.end,
-        );
-
-        let mut path = HashMap::new();
-        for p in result.unwrap().0 {
-            for x in p {
-                path.insert(x.0, x.1);
+    // Searches the states in the order of their cost from the start until the cost of the
+    // best path is exceeded, the best path is the chain of parents from the first end state.
+    fn best_path_score(&mut self) -> Result<usize> {
+        if let Some(score) = self.best_score {
+            return Ok(score);
+        }
+
+        let mut parents = HashMap::new();
+        let mut best = None;
+        let start = (self.start, Direction::Right);
+        for item in dijkstra_reach(&start, |&(pos, dir)| self.successors(pos, dir)) {
+            if best.is_some_and(|(_, score)| item.total_cost > score) {
+                break;
             }
+            if best.is_none() && item.node.0 == self.end {
+                best = Some((item.node, item.total_cost));
+            }
+            parents.insert(item.node, (item.parent, item.total_cost));
         }
 
-        self.path = Some(path.clone());
+        let (end, score) = best.ok_or_else(|| miette!("No path from Start to End."))?;
+        let mut path = iter::successors(Some(end), |state| parents[state].0).collect::<Vec<_>>();
+        path.reverse();
+
+        self.path = path;
+        self.from_start = parents
+            .into_iter()
+            .map(|(state, (_, cost))| (state, cost))
+            .collect();
+        self.best_score = Some(score);
 
-        path.len()
+        Ok(score)
     }
 
-    fn print(&self, with_direction: bool) {
-        for y in 0..self.height {
-            for x in 0..self.width {
-                let pos = IVec2 {
-                    x: x as i32,
-                    y: y as i32,
-                };
-                if self.obstacles.contains(&pos) {
-                    print!("{}", "#".red())
-                } else if self.start == pos {
-                    print!("{}", "S".yellow());
-                } else if self.end == pos {
-                    print!("{}", "E".green());
-                } else if let Some(path) = &self.path {
-                    if path.contains_key(&pos) {
-                        if with_direction {
-                            match path.get(&pos).unwrap() {
-                                &IVec2::X => print!("{}", '>'.green()),
-                                &IVec2::NEG_X => print!("{}", '<'.green()),
-                                &IVec2::Y => print!("{}", 'v'.green()),
-                                &IVec2::NEG_Y => print!("{}", '^'.green()),
-
-                                _ => panic!("unexpected direction"),
-                            }
-                        } else {
-                            print!("{}", 'O'.green());
-                        }
-                    } else {
-                        print!("{}", ".".white());
-                    }
-                } else {
-                    print!("{}", ".".white());
-                }
-            }
-            println!();
-        }
+    // A state is on a best path if the cheapest ways from the start to it and from it to the
+    // end add up to the best score, so the search from the end stops at the best score as well.
+    fn nr_best_path_tiles(&mut self) -> Result<usize> {
+        let best_score = self.best_path_score()?;
+
+        self.best_tiles = dijkstra_reach(&None, |&state| self.predecessors(state))
+            .take_while(|item| item.total_cost <= best_score)
+            .filter_map(|item| {
+                let state = item.node?;
+                let from_start_cost = self.from_start.get(&state)?;
+                (from_start_cost + item.total_cost == best_score).then_some(state.0)
+            })
+            .collect();
+
+        Ok(self.best_tiles.len())
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+// the tiles of a path, the turns on a tile are steps of the path as well
+fn positions(steps: &[(Point, Direction)]) -> Vec<Point> {
+    steps.iter().map(|&(pos, _)| pos).dedup().collect()
+}
+
+impl Render for Maze {
+    fn canvas(&self) -> Canvas {
+        Canvas::from_chars(&self.tiles)
+            .visited(self.best_tiles.iter().copied())
+            .path(self.path.iter().copied())
+    }
+}
 
-    fn example() -> &'static str {
-        "###############
+const EXAMPLE_FIRST: &str = "###############
 #.......#....E#
 #.#.###.#.###.#
 #.....#.#...#.#
@@ -226,11 +205,9 @@ mod tests {
 #.....#...#.#.#
 #.###.#.#.#.#.#
 #S..#.....#...#
-###############"
-    }
+###############";
 
-    fn example2() -> &'static str {
-        "#################
+const EXAMPLE_SECOND: &str = "#################
 #...#...#...#..E#
 #.#.#.#.#.#.#.#.#
 #.#.#.#...#...#.#
@@ -246,28 +223,40 @@ mod tests {
 #.#.#.........#.#
 #.#.#.#########.#
 #S#.............#
-#################"
-    }
+#################";
+
+const EXAMPLES: &[Example] = &[
+    Example::new("first", EXAMPLE_FIRST)
+        .part1("7036")
+        .part2("45"),
+    Example::new("second", EXAMPLE_SECOND)
+        .part1("11048")
+        .part2("64"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answers::assert_input_answer;
+    use crate::util::{assert_example_answers, Part};
 
     #[test]
     fn test_part1_with_examples() {
-        assert_eq!(solve_part1(example()), "7036");
-        assert_eq!(solve_part1(example2()), "11048");
+        assert_example_answers(&get_day(), Part::One);
     }
 
     #[test]
     fn test_part1_with_input() {
-        assert_eq!(solve_part1(&get_day().read_input()), "109516");
+        assert_input_answer(&get_day(), Part::One);
     }
 
     #[test]
     fn test_part2_with_examples() {
-        assert_eq!(solve_part2(example()), "45");
-        assert_eq!(solve_part2(example2()), "64");
+        assert_example_answers(&get_day(), Part::Two);
     }
 
     #[test]
     fn test_part2_with_input() {
-        assert_eq!(solve_part2(&get_day().read_input()), "568");
+        assert_input_answer(&get_day(), Part::Two);
     }
 }
@@ -1,28 +1,38 @@
+use std::fmt;
+use std::iter;
+
+use miette::{bail, miette, Result};
 use num_derive::FromPrimitive;
-use regex::Regex;
 
-use crate::util::Day;
+use crate::util::parse;
+use crate::util::{Answer, Day, Example, Params};
+
+pub mod asm;
+pub mod debugger;
 
 const DAY_NR: u8 = 17;
 const PROBLEM_TITLE: &str = "Chronospatial Computer";
 
 pub fn get_day() -> Day {
-    Day::new(DAY_NR, PROBLEM_TITLE, solve_part1, solve_part2)
+    Day::new(DAY_NR, PROBLEM_TITLE, solve_part1, solve_part2).with_examples(EXAMPLES)
 }
 
-fn solve_part1(input: &str) -> String {
-    let mut computer = Computer::new(input);
+fn solve_part1(input: &str, _params: &Params) -> Result<Answer> {
+    let mut computer = Computer::new(input)?;
     let output = computer.run();
-    output.to_string()
+    Ok(output.into())
 }
 
-fn solve_part2(input: &str) -> String {
-    let computer = Computer::new(input);
-    let reg_a = part2_from_felix(computer);
-    reg_a.to_string()
+fn solve_part2(input: &str, _params: &Params) -> Result<Answer> {
+    let mut computer = Computer::new(input)?;
+    check_quine_shape(&computer)?;
+    let reg_a = find_quine_reg_a(&mut computer)
+        .ok_or_else(|| miette!("No value of register A makes the program output itself."))?;
+    Ok(reg_a.into())
 }
 
-#[derive(Debug, FromPrimitive)]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, FromPrimitive)]
 enum OpCode {
     ADV = 0,
     BXL = 1,
@@ -34,6 +44,68 @@ enum OpCode {
     CDV = 7,
 }
 
+impl OpCode {
+    // the other instructions take a literal operand or ignore it
+    fn has_combo_operand(self) -> bool {
+        matches!(
+            self,
+            OpCode::ADV | OpCode::BST | OpCode::OUT | OpCode::BDV | OpCode::CDV
+        )
+    }
+}
+
+impl fmt::Display for OpCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mnemonic = match self {
+            OpCode::ADV => "adv",
+            OpCode::BXL => "bxl",
+            OpCode::BST => "bst",
+            OpCode::JNZ => "jnz",
+            OpCode::BXC => "bxc",
+            OpCode::OUT => "out",
+            OpCode::BDV => "bdv",
+            OpCode::CDV => "cdv",
+        };
+        write!(f, "{}", mnemonic)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Register {
+    A,
+    B,
+    C,
+}
+
+impl Register {
+    const ALL: [Register; 3] = [Register::A, Register::B, Register::C];
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_uppercase().as_str() {
+            "A" => Some(Register::A),
+            "B" => Some(Register::B),
+            "C" => Some(Register::C),
+            _ => None,
+        }
+    }
+
+    // the register a combo operand reads
+    fn of_combo(operand: u8) -> Option<Self> {
+        match operand {
+            4 => Some(Register::A),
+            5 => Some(Register::B),
+            6 => Some(Register::C),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Register {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
 #[derive(Debug)]
 struct Operation {
     instruction: OpCode,
@@ -51,54 +123,106 @@ struct Computer {
 }
 
 impl Computer {
-    fn new(input: &str) -> Self {
-        let parts: Vec<&str> = input.split("\n\n").collect();
+    fn new(input: &str) -> Result<Self> {
+        let sections = parse::sections(input);
+        let [registers, program_section] = sections[..] else {
+            bail!(
+                help = "The registers come first, then a blank line and the program.",
+                "Expected the registers and the program separated by a blank line."
+            );
+        };
 
         // parse registers
         let mut reg_a: Option<u64> = None;
         let mut reg_b: Option<u64> = None;
         let mut reg_c: Option<u64> = None;
-        let re = Regex::new(r"Register ([ABC]): (\d+)").unwrap();
-        for (_, [reg, val]) in re.captures_iter(parts[0]).map(|c| c.extract()) {
-            match reg {
-                "A" => reg_a = Some(val.parse::<u64>().unwrap()),
-                "B" => reg_b = Some(val.parse::<u64>().unwrap()),
-                "C" => reg_c = Some(val.parse::<u64>().unwrap()),
-                _ => panic!("Unexpected register"),
-            }
+        for line in registers.lines() {
+            let (name, value) = line.key_value()?;
+            let reg = match name.as_str() {
+                "Register A" => &mut reg_a,
+                "Register B" => &mut reg_b,
+                "Register C" => &mut reg_c,
+                _ => {
+                    return Err(name
+                        .error(format!("unknown register '{}'", name))
+                        .with_help("The registers are 'Register A', 'Register B' and 'Register C'.")
+                        .into())
+                }
+            };
+            *reg = Some(value.parse()?);
         }
 
         // parse program
+        let (name, program_str) = program_section.key_value()?;
+        if name.as_str() != "Program" {
+            return Err(name
+                .error("expected the program to start with 'Program: '")
+                .into());
+        }
+        let items = program_str
+            .split(',')
+            .map(|item| item.trim())
+            .collect::<Vec<_>>();
+        if items.len() % 2 != 0 {
+            return Err(items[items.len() - 1]
+                .error("missing operand")
+                .with_help(format!(
+                    "Every opcode is followed by its operand, the program has {} numbers.",
+                    items.len()
+                ))
+                .into());
+        }
+
         let mut program = vec![];
-        let mut program_raw: Vec<u64> = vec![];
-        let program_str = parts[1].split_whitespace().collect::<Vec<&str>>()[1];
-        let mut iter = program_str.split(',');
-        loop {
-            match iter.next() {
-                None => break,
-                Some(opcode_str) => {
-                    let opcode = opcode_str.parse::<u8>().unwrap();
-                    let instruction: OpCode = num::FromPrimitive::from_u8(opcode).unwrap();
-
-                    let operand = iter.next().unwrap().parse::<u8>().unwrap();
-                    program_raw.push(opcode as u64);
-                    program_raw.push(operand as u64);
-                    program.push(Operation {
-                        instruction,
-                        operand,
-                    });
-                }
+        let mut program_raw = vec![];
+        for op in items.chunks(2) {
+            let opcode: u8 = op[0].parse()?;
+            let instruction: OpCode = num::FromPrimitive::from_u8(opcode).ok_or_else(|| {
+                op[0]
+                    .error(format!("invalid opcode {}", opcode))
+                    .with_help("The opcodes are 0 to 7.")
+            })?;
+            let operand: u8 = op[1].parse()?;
+            if operand > 7 {
+                return Err(op[1]
+                    .error(format!("invalid operand {}", operand))
+                    .with_help("The operands are 0 to 7.")
+                    .into());
+            }
+            if operand == 7 && instruction.has_combo_operand() {
+                return Err(op[1]
+                    .error(format!("reserved combo operand 7 of {}", instruction))
+                    .with_help("Combo operand 7 does not appear in valid programs.")
+                    .into());
+            }
+            if instruction == OpCode::JNZ && !operand.is_multiple_of(2) {
+                return Err(op[1]
+                    .error(format!("jump target {} is not an instruction", operand))
+                    .with_help("Instructions start at the even addresses of the program.")
+                    .into());
             }
+            program_raw.push(opcode as u64);
+            program_raw.push(operand as u64);
+            program.push(Operation {
+                instruction,
+                operand,
+            });
         }
 
-        Computer {
+        let missing = |reg: &str| {
+            registers
+                .error(format!("register {} is missing", reg))
+                .with_help(format!("Add a line 'Register {}: <value>'.", reg))
+        };
+
+        Ok(Computer {
             program,
             program_raw,
-            reg_a: reg_a.unwrap(),
-            reg_b: reg_b.unwrap(),
-            reg_c: reg_c.unwrap(),
+            reg_a: reg_a.ok_or_else(|| missing("A"))?,
+            reg_b: reg_b.ok_or_else(|| missing("B"))?,
+            reg_c: reg_c.ok_or_else(|| missing("C"))?,
             ip: 0,
-        }
+        })
     }
 
     fn operand_value(&self, operand: u8) -> u64 {
@@ -109,153 +233,231 @@ impl Computer {
             4 => self.reg_a,
             5 => self.reg_b,
             6 => self.reg_c,
-            _ => panic!("unexpected operand"),
+            _ => unreachable!("combo operand 7 is rejected by the parser"),
         }
     }
 
-    fn run(&mut self) -> String {
-        let mut output = vec![];
-        self.ip = 0;
-
-        while self.ip < self.program.len() {
-            let mut jumped = false;
-
-            let op = &self.program[self.ip];
-            match op.instruction {
-                OpCode::ADV => {
-                    let nom = self.reg_a;
-                    let denom = 2u64.pow(self.operand_value(op.operand) as u32);
-                    self.reg_a = nom / denom;
-                }
-
-                OpCode::BXL => {
-                    self.reg_b = self.reg_b ^ op.operand as u64;
-                }
+    // the literal operand or the value of the combo operand
+    fn value(&self, op: &Operation) -> u64 {
+        match op.instruction.has_combo_operand() {
+            true => self.operand_value(op.operand),
+            false => op.operand as u64,
+        }
+    }
 
-                OpCode::BST => {
-                    self.reg_b = self.operand_value(op.operand) % 8;
-                }
+    fn register(&self, reg: Register) -> u64 {
+        match reg {
+            Register::A => self.reg_a,
+            Register::B => self.reg_b,
+            Register::C => self.reg_c,
+        }
+    }
 
-                OpCode::JNZ => {
-                    if self.reg_a != 0 {
-                        assert!(op.operand % 2 == 0);
-                        self.ip = op.operand as usize / 2;
-                        jumped = true;
-                    }
-                }
+    fn set_register(&mut self, reg: Register, value: u64) {
+        match reg {
+            Register::A => self.reg_a = value,
+            Register::B => self.reg_b = value,
+            Register::C => self.reg_c = value,
+        }
+    }
 
-                OpCode::BXC => {
-                    self.reg_b = self.reg_b ^ self.reg_c;
-                    // igore operand
-                }
+    // A divided by 2 to the power of the operand value, shifted out completely for large values
+    fn divide_a(&self, value: u64) -> u64 {
+        u32::try_from(value)
+            .ok()
+            .and_then(|shift| self.reg_a.checked_shr(shift))
+            .unwrap_or(0)
+    }
 
-                OpCode::OUT => {
-                    let val = self.operand_value(op.operand) % 8;
-                    output.push(val);
-                }
+    fn run(&mut self) -> String {
+        self.outputs()
+            .into_iter()
+            .map(|v| v.to_string())
+            .collect::<Vec<_>>()
+            .join(",")
+    }
 
-                OpCode::BDV => {
-                    let nom = self.reg_a;
-                    let denom = 2u64.pow(self.operand_value(op.operand) as u32);
-                    self.reg_b = nom / denom;
-                }
+    fn outputs(&mut self) -> Vec<u64> {
+        self.ip = 0;
+        iter::from_fn(|| self.step()).flatten().collect()
+    }
 
-                OpCode::CDV => {
-                    let nom = self.reg_a;
-                    let denom = 2u64.pow(self.operand_value(op.operand) as u32);
-                    self.reg_c = nom / denom;
+    /// Executes the instruction at the instruction pointer and returns its output, None if the
+    /// program has halted.
+    fn step(&mut self) -> Option<Option<u64>> {
+        let op = self.program.get(self.ip)?;
+        let (instruction, value) = (op.instruction, self.value(op));
+        let mut output = None;
+        self.ip += 1;
+
+        match instruction {
+            OpCode::ADV => self.reg_a = self.divide_a(value),
+            OpCode::BXL => self.reg_b ^= value,
+            OpCode::BST => self.reg_b = value % 8,
+            OpCode::JNZ => {
+                if self.reg_a != 0 {
+                    // odd jump targets are rejected by the parser
+                    self.ip = value as usize / 2;
                 }
             }
-
-            if !jumped {
-                self.ip += 1;
-            }
+            // the operand is ignored
+            OpCode::BXC => self.reg_b ^= self.reg_c,
+            OpCode::OUT => output = Some(value % 8),
+            OpCode::BDV => self.reg_b = self.divide_a(value),
+            OpCode::CDV => self.reg_c = self.divide_a(value),
         }
 
-        output
-            .into_iter()
-            .map(|v| v.to_string())
-            .collect::<Vec<_>>()
-            .join(",")
+        Some(output)
     }
 }
 
-fn compute_fast(mut a: u64) -> Vec<u64> {
-    // bst 4(A)         => B = A mod 8
-    // bxl 1(1)         => B = B xor 1
-    // cdv 5(B)         => C = A / 2^B
-    // bxc 6(ignored)   => B = B xor C
-    // adv 3(3)         => A = A / 2^3
-    // bxl 4(4)         => B = B xor 4
-    // out 5(B)
-    // jnz 0
-
-    let mut out = vec![];
-    let mut b;
-    let mut c;
-    while a != 0 {
-        b = a % 8;
-        b = b ^ 1;
-        c = a >> b;
-        b = b ^ c;
-        a = a >> 3;
-        b = b ^ 4;
-        out.push(b % 8);
+// The search for register A only works for programs that loop until A is 0, shift A right by 3
+// bits in every loop and output once per loop. The output of a loop then only depends on the
+// bits of A that are not shifted out yet.
+fn check_quine_shape(computer: &Computer) -> Result<()> {
+    let operands = |instruction: OpCode| {
+        computer
+            .program
+            .iter()
+            .filter(|op| op.instruction == instruction)
+            .map(|op| op.operand)
+            .collect::<Vec<_>>()
+    };
+    let help = "Part 2 needs a program that ends with 'jnz 0', shifts A with 'adv 3' once and \
+        outputs once per loop.";
+
+    let ends_with_jump = computer
+        .program
+        .last()
+        .is_some_and(|op| op.instruction == OpCode::JNZ);
+    if !ends_with_jump || operands(OpCode::JNZ) != [0] {
+        bail!(
+            help = help,
+            "The program does not loop back to its start once at its end."
+        );
+    }
+    if operands(OpCode::ADV) != [3] {
+        bail!(
+            help = help,
+            "The program does not shift register A by 3 bits once per loop."
+        );
+    }
+    if operands(OpCode::OUT).len() != 1 {
+        bail!(
+            help = help,
+            "The program does not output exactly once per loop."
+        );
     }
-    out
+    Ok(())
 }
 
-fn part2_from_felix(computer: Computer) -> u64 {
-    let mut a = 0;
-    let prog_len = computer.program_raw.len();
-    for i in 0..prog_len {
-        let expected_out = &computer.program_raw[prog_len - i - 1..];
-        let mut offset = 0;
-        loop {
-            let next_a = (a << 3) + offset;
-            let result = compute_fast(next_a);
-            if result == expected_out {
-                a = next_a;
-                break;
-            }
-            offset += 1;
+// Builds register A 3 bits at a time, from the last output of the program backwards, so every
+// step only has 8 candidates. The smallest candidates are tried first, so the first complete
+// value is the lowest.
+fn find_quine_reg_a(computer: &mut Computer) -> Option<u64> {
+    let (reg_b, reg_c) = (computer.reg_b, computer.reg_c);
+    let program = computer.program_raw.clone();
+
+    fn search(
+        computer: &mut Computer,
+        program: &[u64],
+        regs: (u64, u64),
+        a: u64,
+        found: usize,
+    ) -> Option<u64> {
+        if found == program.len() {
+            return Some(a);
         }
+        let expected = &program[program.len() - found - 1..];
+        (0..8).find_map(|bits| {
+            let next_a = (a << 3) | bits;
+            computer.reg_a = next_a;
+            (computer.reg_b, computer.reg_c) = regs;
+            match computer.outputs() == expected {
+                true => search(computer, program, regs, next_a, found + 1),
+                false => None,
+            }
+        })
     }
-    a
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    search(computer, &program, (reg_b, reg_c), 0, 0)
+}
 
-    fn example() -> &'static str {
-        "Register A: 729
+const EXAMPLE: &str = "Register A: 729
 Register B: 0
 Register C: 0
 
-Program: 0,1,5,4,3,0"
-    }
+Program: 0,1,5,4,3,0";
 
-    fn example2() -> &'static str {
-        "Register A: 2024
+const EXAMPLE_QUINE: &str = "Register A: 2024
 Register B: 0
 Register C: 0
 
-Program: 0,3,5,4,3,0"
-    }
+Program: 0,3,5,4,3,0";
+
+const EXAMPLES: &[Example] = &[
+    Example::new("example", EXAMPLE).part1("4,6,3,5,6,3,5,2,1,0"),
+    Example::new("quine", EXAMPLE_QUINE)
+        .part1("5,7,3,0")
+        .part2("117440"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answers::assert_input_answer;
+    use crate::util::{assert_example_answers, Part};
 
     #[test]
     fn test_part1_with_examples() {
-        assert_eq!(solve_part1(example()), "4,6,3,5,6,3,5,2,1,0");
+        assert_example_answers(&get_day(), Part::One);
     }
 
     #[test]
     fn test_part1_with_input() {
-        assert_eq!(solve_part1(&get_day().read_input()), "7,3,0,5,7,1,4,0,5");
+        assert_input_answer(&get_day(), Part::One);
+    }
+
+    #[test]
+    fn test_part2_with_examples() {
+        assert_example_answers(&get_day(), Part::Two);
     }
 
     #[test]
     fn test_part2_with_input() {
-        assert_eq!(solve_part2(&get_day().read_input()), "202972175280682");
+        assert_input_answer(&get_day(), Part::Two);
+    }
+
+    #[test]
+    fn test_program_shape() {
+        // shifts A by one bit only
+        let computer = Computer::new(EXAMPLE).unwrap();
+        let error = check_quine_shape(&computer).unwrap_err();
+        assert!(error.to_string().contains("shift register A by 3 bits"));
+        assert!(error.help().is_some());
+
+        let input = EXAMPLE_QUINE.replace("3,0", "3,2");
+        let error = check_quine_shape(&Computer::new(&input).unwrap()).unwrap_err();
+        assert!(error.to_string().contains("loop back"));
+    }
+
+    #[test]
+    fn test_odd_program() {
+        let input = "Register A: 0\nRegister B: 0\nRegister C: 0\n\nProgram: 0,1,5";
+        let error = Computer::new(input).unwrap_err();
+        assert_eq!(error.to_string(), "line 5, column 14: missing operand");
+        let span = error.labels().unwrap().next().unwrap();
+        assert_eq!(&input[span.offset()..span.offset() + span.len()], "5");
+    }
+
+    #[test]
+    fn test_odd_jump() {
+        let input = "Register A: 5\nRegister B: 0\nRegister C: 0\n\nProgram: 5,4,3,1";
+        let error = Computer::new(input).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 5, column 16: jump target 1 is not an instruction"
+        );
+        assert!(error.help().is_some());
     }
 }
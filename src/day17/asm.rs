@@ -0,0 +1,248 @@
+use std::collections::{BTreeSet, HashMap};
+
+use miette::{bail, Result};
+
+use super::{Computer, OpCode, Register};
+use crate::util::parse::{self, Span};
+
+// the name of a jump target in the disassembly
+fn label(addr: usize) -> String {
+    format!("L{}", addr)
+}
+
+/// Disassembles the program of the input into a mnemonic per line. Combo operands that read a
+/// register are shown as `A`, `B` or `C`, jump targets get labels and the registers that are not
+/// 0 are set with `set` lines.
+pub fn disassemble(input: &str) -> Result<String> {
+    let computer = Computer::new(input)?;
+    let targets = computer
+        .program
+        .iter()
+        .filter(|op| op.instruction == OpCode::JNZ)
+        .map(|op| op.operand as usize)
+        .filter(|&addr| addr.is_multiple_of(2) && addr < 2 * computer.program.len())
+        .collect::<BTreeSet<_>>();
+
+    let mut text = String::new();
+    for reg in Register::ALL {
+        let value = computer.register(reg);
+        if value != 0 {
+            text += &format!("set {} {}\n", reg, value);
+        }
+    }
+    for (i, op) in computer.program.iter().enumerate() {
+        if targets.contains(&(2 * i)) {
+            text += &format!("{}:\n", label(2 * i));
+        }
+        let operand = match op.instruction {
+            OpCode::JNZ if targets.contains(&(op.operand as usize)) => label(op.operand as usize),
+            // the operand is ignored, so it is only shown if it is not 0
+            OpCode::BXC if op.operand == 0 => String::new(),
+            instruction if instruction.has_combo_operand() => {
+                match Register::of_combo(op.operand) {
+                    Some(reg) => reg.to_string(),
+                    None => op.operand.to_string(),
+                }
+            }
+            _ => op.operand.to_string(),
+        };
+        text += format!("    {} {}", op.instruction, operand).trim_end();
+        text.push('\n');
+    }
+    Ok(text)
+}
+
+/// Assembles the text of [`disassemble`] into the puzzle input with the registers and the
+/// program. A jump takes a label or an address, `;` and `#` start comments.
+pub fn assemble(source: &str) -> Result<String> {
+    // a jump to a label is resolved once all labels are known
+    enum Operand<'a> {
+        Value(u8),
+        Label(Span<'a>),
+    }
+
+    let mut registers = [0; 3];
+    let mut labels = HashMap::new();
+    let mut program = vec![];
+    for line in parse::lines(source) {
+        let code = match line.as_str().find([';', '#']) {
+            Some(comment) => line.slice(0..comment),
+            None => line,
+        };
+        let mut tokens = code.tokens();
+        let Some(first) = tokens.next() else {
+            continue;
+        };
+        let args = tokens.collect::<Vec<_>>();
+
+        if let Some(name) = first.as_str().strip_suffix(':') {
+            if name.is_empty() || !args.is_empty() {
+                return Err(code
+                    .trim()
+                    .error("expected a label")
+                    .with_help("A label stands on its own line, e.g. 'loop:'.")
+                    .into());
+            }
+            if labels.insert(name, 2 * program.len()).is_some() {
+                return Err(first.error(format!("duplicate label '{}'", name)).into());
+            }
+            continue;
+        }
+
+        if first.as_str().eq_ignore_ascii_case("set") {
+            let [reg, value] = args[..] else {
+                return Err(code
+                    .trim()
+                    .error("expected 'set <register> <value>'")
+                    .into());
+            };
+            let reg = Register::from_name(reg.as_str()).ok_or_else(|| {
+                reg.error(format!("unknown register '{}'", reg))
+                    .with_help("The registers are A, B and C.")
+            })?;
+            registers[reg as usize] = value.parse()?;
+            continue;
+        }
+
+        let instruction = (0..8)
+            .filter_map(num::FromPrimitive::from_u8)
+            .find(|op: &OpCode| op.to_string().eq_ignore_ascii_case(first.as_str()))
+            .ok_or_else(|| {
+                first
+                    .error(format!("unknown instruction '{}'", first))
+                    .with_help("The instructions are adv, bxl, bst, jnz, bxc, out, bdv and cdv.")
+            })?;
+        let operand = match args[..] {
+            [] if instruction == OpCode::BXC => Operand::Value(0),
+            [arg] if instruction.has_combo_operand() => {
+                let operand = match Register::from_name(arg.as_str()) {
+                    Some(reg) => Some(4 + reg as u8),
+                    None => arg.as_str().parse().ok().filter(|&n: &u8| n <= 3),
+                };
+                Operand::Value(operand.ok_or_else(|| {
+                    arg.error(format!("invalid combo operand '{}'", arg))
+                        .with_help("Combo operands are 0 to 3 or the registers A, B and C.")
+                })?)
+            }
+            [arg]
+                if instruction == OpCode::JNZ
+                    && !arg.as_str().starts_with(|ch: char| ch.is_ascii_digit()) =>
+            {
+                Operand::Label(arg)
+            }
+            [arg] => {
+                let operand: u8 = arg.parse()?;
+                if operand > 7 {
+                    return Err(arg
+                        .error(format!("invalid operand {}", operand))
+                        .with_help("The operands are 0 to 7.")
+                        .into());
+                }
+                if instruction == OpCode::JNZ && !operand.is_multiple_of(2) {
+                    return Err(arg
+                        .error(format!("jump target {} is not an instruction", operand))
+                        .with_help("Instructions start at the even addresses of the program.")
+                        .into());
+                }
+                Operand::Value(operand)
+            }
+            _ => {
+                return Err(code
+                    .trim()
+                    .error(format!("expected one operand for {}", instruction))
+                    .into())
+            }
+        };
+        program.push((instruction, operand));
+    }
+
+    if program.is_empty() {
+        bail!("The program has no instructions.");
+    }
+    let mut numbers = vec![];
+    for (instruction, operand) in program {
+        let operand = match operand {
+            Operand::Value(value) => value,
+            Operand::Label(name) => {
+                let addr = labels.get(name.as_str()).ok_or_else(|| {
+                    name.error(format!("unknown label '{}'", name))
+                        .with_help("Define the label on its own line, e.g. 'loop:'.")
+                })?;
+                u8::try_from(*addr)
+                    .ok()
+                    .filter(|&addr| addr <= 7)
+                    .ok_or_else(|| {
+                        name.error(format!(
+                            "label '{}' is out of reach at address {}",
+                            name, addr
+                        ))
+                        .with_help("A jump can only reach the addresses 0 to 7.")
+                    })?
+            }
+        };
+        numbers.push((instruction as u8).to_string());
+        numbers.push(operand.to_string());
+    }
+
+    let [a, b, c] = registers;
+    Ok(format!(
+        "Register A: {}\nRegister B: {}\nRegister C: {}\n\nProgram: {}",
+        a,
+        b,
+        c,
+        numbers.join(",")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day17::{EXAMPLE, EXAMPLE_QUINE};
+
+    #[test]
+    fn test_disassemble() {
+        assert_eq!(
+            disassemble(EXAMPLE).unwrap(),
+            "set A 729\nL0:\n    adv 1\n    out A\n    jnz L0\n"
+        );
+        for input in [EXAMPLE, EXAMPLE_QUINE] {
+            assert_eq!(assemble(&disassemble(input).unwrap()).unwrap(), input);
+        }
+    }
+
+    #[test]
+    fn test_assemble() {
+        // outputs the octal digits of A from the lowest, each followed by a 3
+        let source = "# countdown\nset A 24\nset b 5\nloop:\n  bst A\n  bxc\n  out B ; twice\n  out 3\n  adv 3\n  jnz loop\n";
+        let input = assemble(source).unwrap();
+        assert!(input.ends_with("Program: 2,4,4,0,5,5,5,3,0,3,3,0"));
+        assert_eq!(Computer::new(&input).unwrap().run(), "0,3,3,3");
+        assert_eq!(assemble(&disassemble(&input).unwrap()).unwrap(), input);
+
+        let error = assemble("out 7").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 5: invalid combo operand '7'"
+        );
+        let error = assemble("adv 1\nstart:\njnz end").unwrap_err();
+        assert_eq!(error.to_string(), "line 3, column 5: unknown label 'end'");
+        assert_eq!(
+            assemble("ADV 1\nOut a\nJNZ 0").unwrap(),
+            assemble("adv 1\nout A\njnz 0").unwrap()
+        );
+        assert!(assemble("mul 2").is_err());
+        assert!(assemble("bxl 8").is_err());
+        let error = assemble("set A 5\nout A\njnz 1").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 3, column 5: jump target 1 is not an instruction"
+        );
+        assert!(assemble("; nothing").is_err());
+
+        let error = Computer::new("Register A: 0\nRegister B: 0\nRegister C: 0\n\nProgram: 5,7");
+        assert!(error
+            .unwrap_err()
+            .to_string()
+            .contains("reserved combo operand 7"));
+    }
+}
@@ -0,0 +1,380 @@
+use std::fmt;
+use std::fs;
+use std::io::{BufRead, Write};
+use std::path::Path;
+
+use miette::{bail, miette, IntoDiagnostic, Result};
+use serde_json::json;
+
+use super::{Computer, OpCode, Register};
+
+/// An executed instruction with the register changes and the output it caused.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Step {
+    // the address in the program, i.e. twice the index of the instruction
+    ip: usize,
+    instruction: OpCode,
+    operand: u8,
+    // the literal operand or the value of the combo operand
+    value: u64,
+    changes: Vec<(Register, u64, u64)>,
+    output: Option<u64>,
+}
+
+impl Step {
+    fn to_json(&self) -> serde_json::Value {
+        let changes = self
+            .changes
+            .iter()
+            .map(|(reg, old, new)| (reg.to_string(), json!([old, new])))
+            .collect::<serde_json::Map<_, _>>();
+        json!({
+            "ip": self.ip,
+            "instruction": self.instruction.to_string(),
+            "operand": self.operand,
+            "value": self.value,
+            "changes": changes,
+            "output": self.output,
+        })
+    }
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let operand = match Register::of_combo(self.operand) {
+            Some(reg) if self.instruction.has_combo_operand() => {
+                format!("{} ({}={})", self.operand, reg, self.value)
+            }
+            _ => self.operand.to_string(),
+        };
+        let mut line = format!("{:>3}: {} {:<14}", self.ip, self.instruction, operand);
+        for (reg, old, new) in &self.changes {
+            line += &format!(" {} {} -> {}", reg, old, new);
+        }
+        if let Some(value) = self.output {
+            line += &format!(" out {}", value);
+        }
+        write!(f, "{}", line.trim_end())
+    }
+}
+
+// `continue` gives up after this many steps, in case the program never halts
+const MAX_STEPS: usize = 1_000_000;
+
+const DEBUGGER_HELP: &str = "\
+step [N]        execute the next N instructions (s)
+continue        run to the next breakpoint, watch or the end of the program (c)
+break IP        stop before the instruction at address IP (b)
+break out N     stop once the program has output N values
+watch R         stop when register R changes (w)
+delete          remove all breakpoints and watches
+set R VALUE     change the value of register R
+regs            show the registers and the output so far (r)
+trace           show all executed instructions
+quit            stop debugging (q)";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Breakpoint {
+    // before the instruction at the address
+    Ip(usize),
+    // once the program has output this many values
+    Outputs(usize),
+}
+
+// why `continue` stopped
+enum Stop {
+    Halted,
+    Breakpoint(Breakpoint),
+    Watch(Register, u64, u64),
+    StepLimit,
+}
+
+struct Debugger {
+    computer: Computer,
+    breakpoints: Vec<Breakpoint>,
+    watches: Vec<Register>,
+    output: Vec<u64>,
+    trace: Vec<Step>,
+}
+
+impl Debugger {
+    fn new(mut computer: Computer) -> Self {
+        computer.ip = 0;
+        Debugger {
+            computer,
+            breakpoints: vec![],
+            watches: vec![],
+            output: vec![],
+            trace: vec![],
+        }
+    }
+
+    fn step(&mut self) -> Option<&Step> {
+        let ip = self.computer.ip;
+        let op = self.computer.program.get(ip)?;
+        let (instruction, operand) = (op.instruction, op.operand);
+        let value = self.computer.value(op);
+        let before = Register::ALL.map(|reg| self.computer.register(reg));
+        let output = self.computer.step()?;
+
+        let changes = Register::ALL
+            .into_iter()
+            .zip(before)
+            .map(|(reg, old)| (reg, old, self.computer.register(reg)))
+            .filter(|&(_, old, new)| old != new)
+            .collect();
+        self.output.extend(output);
+        self.trace.push(Step {
+            ip: 2 * ip,
+            instruction,
+            operand,
+            value,
+            changes,
+            output,
+        });
+        self.trace.last()
+    }
+
+    // Runs at least one instruction, then up to the next breakpoint or watched change.
+    fn resume(&mut self) -> Stop {
+        for _ in 0..MAX_STEPS {
+            let Some(step) = self.step().cloned() else {
+                return Stop::Halted;
+            };
+            let change = step
+                .changes
+                .iter()
+                .find(|(reg, _, _)| self.watches.contains(reg));
+            if let Some(&(reg, old, new)) = change {
+                return Stop::Watch(reg, old, new);
+            }
+            let ip = 2 * self.computer.ip;
+            let hit = self
+                .breakpoints
+                .iter()
+                .find(|&&breakpoint| match breakpoint {
+                    Breakpoint::Ip(addr) => addr == ip,
+                    Breakpoint::Outputs(n) => step.output.is_some() && self.output.len() == n,
+                });
+            if let Some(&breakpoint) = hit {
+                return Stop::Breakpoint(breakpoint);
+            }
+        }
+        Stop::StepLimit
+    }
+
+    fn registers(&self) -> String {
+        let output = self
+            .output
+            .iter()
+            .map(|v| v.to_string())
+            .collect::<Vec<_>>();
+        format!(
+            "ip {}  A {}  B {}  C {}  output {}",
+            2 * self.computer.ip,
+            self.computer.reg_a,
+            self.computer.reg_b,
+            self.computer.reg_c,
+            output.join(",")
+        )
+    }
+
+    /// Executes a debugger command, false if the debugging is over.
+    fn execute(&mut self, command: &str, out: &mut impl Write) -> Result<bool> {
+        let number = |arg: Option<&str>, what: &str| -> Result<u64> {
+            let arg = arg.ok_or_else(|| miette!("{} is missing", what))?;
+            arg.parse()
+                .map_err(|_| miette!("invalid {} '{}'", what.to_lowercase(), arg))
+        };
+        let register = |arg: Option<&str>| {
+            let name = arg.unwrap_or_default();
+            Register::from_name(name).ok_or_else(|| {
+                miette!(
+                    help = "The registers are A, B and C.",
+                    "unknown register '{}'",
+                    name
+                )
+            })
+        };
+
+        let mut args = command.split_whitespace();
+        match args.next() {
+            None => (),
+            Some(comment) if comment.starts_with('#') => (),
+            Some("step" | "s") => {
+                let n = match args.next() {
+                    Some(n) => number(Some(n), "Number of steps")?,
+                    None => 1,
+                };
+                for _ in 0..n {
+                    match self.step() {
+                        Some(step) => writeln!(out, "{}", step).into_diagnostic()?,
+                        None => {
+                            writeln!(out, "The program has halted.").into_diagnostic()?;
+                            break;
+                        }
+                    }
+                }
+            }
+            Some("continue" | "c") => {
+                let stop = self.resume();
+                if let Some(step) = self.trace.last() {
+                    writeln!(out, "{}", step).into_diagnostic()?;
+                }
+                let reason = match stop {
+                    Stop::Halted => format!("Halted after {} steps.", self.trace.len()),
+                    Stop::Breakpoint(Breakpoint::Ip(addr)) => format!("Breakpoint at ip {}.", addr),
+                    Stop::Breakpoint(Breakpoint::Outputs(n)) => {
+                        format!("Breakpoint after {} outputs.", n)
+                    }
+                    Stop::Watch(reg, old, new) => {
+                        format!("Register {} changed from {} to {}.", reg, old, new)
+                    }
+                    Stop::StepLimit => format!("Still running after {} steps.", MAX_STEPS),
+                };
+                writeln!(out, "{}", reason).into_diagnostic()?;
+            }
+            Some("break" | "b") => {
+                let breakpoint = match args.next() {
+                    Some("out") => {
+                        Breakpoint::Outputs(number(args.next(), "Output count")? as usize)
+                    }
+                    addr => {
+                        let addr = number(addr, "Address")? as usize;
+                        if !addr.is_multiple_of(2) || addr >= 2 * self.computer.program.len() {
+                            bail!(
+                                help = "Instructions start at the even addresses of the program.",
+                                "no instruction at address {}",
+                                addr
+                            );
+                        }
+                        Breakpoint::Ip(addr)
+                    }
+                };
+                self.breakpoints.push(breakpoint);
+            }
+            Some("watch" | "w") => self.watches.push(register(args.next())?),
+            Some("delete") => {
+                self.breakpoints.clear();
+                self.watches.clear();
+            }
+            Some("set") => {
+                let reg = register(args.next())?;
+                let value = number(args.next(), "Value")?;
+                self.computer.set_register(reg, value);
+            }
+            Some("regs" | "r") => writeln!(out, "{}", self.registers()).into_diagnostic()?,
+            Some("trace") => {
+                for step in &self.trace {
+                    writeln!(out, "{}", step).into_diagnostic()?;
+                }
+            }
+            Some("help" | "h") => writeln!(out, "{}", DEBUGGER_HELP).into_diagnostic()?,
+            Some("quit" | "q") => return Ok(false),
+            Some(command) => bail!(
+                help = "Enter 'help' for the list of commands.",
+                "unknown command '{}'",
+                command
+            ),
+        }
+        Ok(true)
+    }
+}
+
+/// Debugs the program of the input with the commands read line by line. An interactive session
+/// shows a prompt and goes on after a failed command, a script stops at the first one. Returns
+/// the trace of all executed instructions.
+pub fn debug(
+    input: &str,
+    commands: impl BufRead,
+    out: &mut impl Write,
+    interactive: bool,
+) -> Result<Vec<Step>> {
+    let mut debugger = Debugger::new(Computer::new(input)?);
+    if interactive {
+        writeln!(out, "Enter 'help' for the list of commands.").into_diagnostic()?;
+    }
+
+    let mut lines = commands.lines();
+    loop {
+        if interactive {
+            write!(out, "(debug) ").into_diagnostic()?;
+            out.flush().into_diagnostic()?;
+        }
+        let Some(line) = lines.next() else {
+            break;
+        };
+        let line = line.into_diagnostic()?;
+        match debugger.execute(&line, out) {
+            Ok(true) => (),
+            Ok(false) => break,
+            Err(err) if interactive => writeln!(out, "Error: {}", err).into_diagnostic()?,
+            Err(err) => return Err(err.wrap_err(format!("Command '{}' failed.", line.trim()))),
+        }
+    }
+
+    Ok(debugger.trace)
+}
+
+/// Writes the trace as JSON if the path ends with `.json`, as text with a line per step otherwise.
+pub fn write_trace(path: &Path, trace: &[Step]) -> Result<()> {
+    let is_json = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+    let content = match is_json {
+        true => {
+            let steps = trace.iter().map(Step::to_json).collect::<Vec<_>>();
+            format!("{:#}\n", serde_json::Value::Array(steps))
+        }
+        false => trace.iter().map(|step| format!("{}\n", step)).collect(),
+    };
+    fs::write(path, content).map_err(|e| miette!("Failed to write {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day17::EXAMPLE;
+
+    #[test]
+    fn test_debugger() {
+        let script = "break out 2\nc\nregs\ndelete\nwatch a\nc\nbreak 4\nc\nset a 0\ncontinue\nq";
+        let mut out = vec![];
+        let trace = debug(EXAMPLE, script.as_bytes(), &mut out, false).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let lines = out.lines().collect::<Vec<_>>();
+        assert_eq!(lines[1], "Breakpoint after 2 outputs.");
+        assert_eq!(lines[2], "ip 4  A 182  B 0  C 0  output 4,6");
+        assert_eq!(lines[4], "Register A changed from 182 to 91.");
+        assert_eq!(lines[6], "Breakpoint at ip 4.");
+        assert_eq!(lines.last(), Some(&"Halted after 9 steps."));
+        assert_eq!(trace.len(), 9);
+
+        let error = debug(EXAMPLE, "step\njump 2".as_bytes(), &mut vec![], false).unwrap_err();
+        assert_eq!(error.to_string(), "Command 'jump 2' failed.");
+    }
+
+    #[test]
+    fn test_trace() {
+        let input = "Register A: 10\nRegister B: 0\nRegister C: 9\n\nProgram: 2,6,5,5";
+        let mut debugger = Debugger::new(Computer::new(input).unwrap());
+        let step = debugger.step().unwrap();
+        assert_eq!(step.to_string(), "  0: bst 6 (C=9)        B 0 -> 1");
+        assert_eq!(
+            step.to_json(),
+            json!({
+                "ip": 0,
+                "instruction": "bst",
+                "operand": 6,
+                "value": 9,
+                "changes": { "B": [0, 1] },
+                "output": null,
+            })
+        );
+        assert_eq!(
+            debugger.step().unwrap().to_string(),
+            "  2: out 5 (B=1)        out 1"
+        );
+        assert!(debugger.step().is_none());
+    }
+}
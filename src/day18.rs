@@ -1,145 +1,158 @@
-use std::collections::HashSet;
-
+use miette::{miette, Result};
 use pathfinding::prelude::astar;
 
-use crate::util::Day;
+use crate::util::render::{Canvas, Render};
+use crate::util::{export, record};
+use crate::util::{manhattan, parse, Answer, Day, Example, Grid, Param, Params, Point};
 
 const DAY_NR: u8 = 18;
 const PROBLEM_TITLE: &str = "RAM Run";
 
+// size of the memory space and number of bytes that have fallen before the search starts
+const PARAMS: &[Param] = &[
+    Param::new("width", 71),
+    Param::new("height", 71),
+    Param::new("bytes", 1024),
+];
+
 pub fn get_day() -> Day {
     Day::new(DAY_NR, PROBLEM_TITLE, solve_part1, solve_part2)
+        .with_examples(EXAMPLES)
+        .with_params(PARAMS)
 }
 
-fn solve_part1(input: &str) -> String {
-    solve_part1_helper(input, 71, 71, 1024)
-}
-
-fn solve_part1_helper(input: &str, width: usize, height: usize, initial_bytes: usize) -> String {
-    let mem = MemorySpace::new(width, height, initial_bytes, input);
-    mem.minimum_steps_to_exit().unwrap().to_string()
+fn solve_part1(input: &str, params: &Params) -> Result<Answer> {
+    let mem = memory_space(input, params)?;
+    let path = mem
+        .shortest_path()
+        .ok_or_else(|| miette!("The exit can not be reached."))?;
+    let steps = path.len() - 1;
+    export::image("shortest-path", &mem, &[path]);
+    Ok(steps.into())
 }
 
-fn solve_part2(input: &str) -> String {
-    solve_part2_helper(input, 71, 71, 1024)
+fn solve_part2(input: &str, params: &Params) -> Result<Answer> {
+    let mut mem = memory_space(input, params)?;
+    let (pos, last_path) = mem
+        .find_pos_without_exit()
+        .ok_or_else(|| miette!("The exit is never blocked."))?;
+    export::image("blocked", &mem, &[last_path]);
+    Ok(format!("{},{}", pos.x, pos.y).into())
 }
 
-fn solve_part2_helper(input: &str, width: usize, height: usize, initial_bytes: usize) -> String {
-    let mut mem = MemorySpace::new(width, height, initial_bytes, input);
-    let Pos(x, y) = mem.find_pos_without_exit();
-    format!("{},{}", x, y)
-}
-
-#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
-struct Pos(i32, i32);
-
-impl Pos {
-    fn distance(&self, other: &Pos) -> u32 {
-        (self.0.abs_diff(other.0) + self.1.abs_diff(other.1)) as u32
-    }
+fn memory_space(input: &str, params: &Params) -> Result<MemorySpace> {
+    MemorySpace::new(
+        params.get_positive("width")?,
+        params.get_positive("height")?,
+        params.get("bytes")?,
+        input,
+    )
 }
 
 #[derive(Debug)]
 struct MemorySpace {
-    width: usize,
-    height: usize,
     initial_bytes: usize,
-    corrupted: Vec<Pos>,
-    obstacles: HashSet<Pos>,
+    corrupted: Vec<Point>,
+    // true for the tiles with a fallen byte
+    obstacles: Grid<bool>,
 }
 
 impl MemorySpace {
-    fn new(width: usize, height: usize, initial_bytes: usize, input: &str) -> Self {
+    fn new(width: usize, height: usize, initial_bytes: usize, input: &str) -> Result<Self> {
+        let mut obstacles = Grid::new(width, height, false);
         let mut corrupted = vec![];
 
-        for line in input.lines() {
-            let parts = line.split(',').collect::<Vec<&str>>();
-            corrupted.push(Pos(
-                parts[0].parse::<i32>().unwrap(),
-                parts[1].parse::<i32>().unwrap(),
-            ));
+        for line in parse::lines(input) {
+            let (x, y) = line.split_once(",")?;
+            let pos = Point::new(x.parse()?, y.parse()?);
+            if !obstacles.contains(pos) {
+                return Err(line
+                    .error(format!(
+                        "byte position {} is outside the memory space",
+                        line
+                    ))
+                    .into());
+            }
+            corrupted.push(pos);
         }
 
-        let mut obstacles = HashSet::new();
-        for i in 0..initial_bytes {
-            obstacles.insert(corrupted[i].clone());
+        for &pos in corrupted.iter().take(initial_bytes) {
+            obstacles[pos] = true;
         }
 
-        MemorySpace {
-            width,
-            height,
+        Ok(MemorySpace {
             initial_bytes,
             corrupted,
             obstacles,
-        }
+        })
     }
 
-    fn minimum_steps_to_exit(&self) -> Option<usize> {
-        let start = Pos(0, 0);
-        let goal = Pos(self.width as i32 - 1, self.height as i32 - 1);
+    // the positions from the start to the exit, both included
+    fn shortest_path(&self) -> Option<Vec<Point>> {
+        let start = Point::ZERO;
+        let goal = Point::new(
+            self.obstacles.width() as i32 - 1,
+            self.obstacles.height() as i32 - 1,
+        );
 
         let res = astar(
             &start,
-            |p| self.successors(p),
-            |p| p.distance(&goal),
-            |p| *p == goal,
+            |&p| self.successors(p),
+            |&p| manhattan(p, goal),
+            |&p| p == goal,
         );
 
-        match res {
-            Some(path) => Some(path.0.len() - 1),
-            None => None,
-        }
+        res.map(|(path, _)| path)
     }
 
-    fn find_pos_without_exit(&mut self) -> Pos {
-        let mut next_pos;
-
-        loop {
-            next_pos = self.corrupted[self.initial_bytes].clone();
-            self.obstacles.insert(next_pos.clone());
-
-            match self.minimum_steps_to_exit() {
-                Some(_input) => self.initial_bytes += 1,
-                None => break,
+    // the first byte that blocks the exit and the last path to the exit before it fell
+    fn find_pos_without_exit(&mut self) -> Option<(Point, Vec<Point>)> {
+        let mut last_path = vec![];
+        while self.initial_bytes < self.corrupted.len() {
+            let next_pos = self.corrupted[self.initial_bytes];
+            self.obstacles[next_pos] = true;
+            record::frame(self);
+
+            match self.shortest_path() {
+                Some(path) => {
+                    last_path = path;
+                    self.initial_bytes += 1;
+                }
+                None => return Some((next_pos, last_path)),
             };
         }
 
-        next_pos
+        None
     }
 
-    fn successors(&self, p: &Pos) -> Vec<(Pos, u32)> {
-        let mut succ = vec![];
-
-        let &Pos(x, y) = p;
-        let mut candidate;
-
-        candidate = Pos(x - 1, y);
-        if x > 0 && !self.obstacles.contains(&candidate) {
-            succ.push((candidate, 1));
-        }
+    // the byte that is falling while searching for the blocking byte
+    fn falling_byte(&self) -> Option<Point> {
+        self.corrupted
+            .get(self.initial_bytes)
+            .copied()
+            .filter(|&pos| self.obstacles[pos])
+    }
 
-        candidate = Pos(x + 1, y);
-        if x + 1 < self.width as i32 && !self.obstacles.contains(&candidate) {
-            succ.push((candidate, 1));
-        }
-        candidate = Pos(x, y - 1);
-        if y > 0 && !self.obstacles.contains(&candidate) {
-            succ.push((candidate, 1));
-        }
-        candidate = Pos(x, y + 1);
-        if y + 1 < self.height as i32 && !self.obstacles.contains(&candidate) {
-            succ.push((candidate, 1));
-        }
-        succ
+    fn successors(&self, p: Point) -> Vec<(Point, u32)> {
+        self.obstacles
+            .neighbours4(p)
+            .filter(|&next| !self.obstacles[next])
+            .map(|next| (next, 1))
+            .collect()
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl Render for MemorySpace {
+    fn canvas(&self) -> Canvas {
+        Canvas::new(&self.obstacles, |&corrupted| match corrupted {
+            true => '#',
+            false => '.',
+        })
+        .highlight(self.falling_byte())
+    }
+}
 
-    fn example() -> &'static str {
-        "5,4
+const EXAMPLE: &str = "5,4
 4,2
 4,5
 3,0
@@ -163,26 +176,36 @@ mod tests {
 1,0
 0,5
 1,6
-2,0"
-    }
+2,0";
+
+const EXAMPLES: &[Example] = &[Example::new("example", EXAMPLE)
+    .part1("22")
+    .part2("6,1")
+    .params(&[("width", 7), ("height", 7), ("bytes", 12)])];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answers::assert_input_answer;
+    use crate::util::{assert_example_answers, Part};
 
     #[test]
     fn test_part1_with_examples() {
-        assert_eq!(solve_part1_helper(example(), 7, 7, 12), "22");
+        assert_example_answers(&get_day(), Part::One);
     }
 
     #[test]
     fn test_part1_with_input() {
-        assert_eq!(solve_part1(&get_day().read_input()), "454");
+        assert_input_answer(&get_day(), Part::One);
     }
 
     #[test]
     fn test_part2_with_examples() {
-        assert_eq!(solve_part2(example()), "6,1");
+        assert_example_answers(&get_day(), Part::Two);
     }
 
     #[test]
     fn test_part2_with_input() {
-        assert_eq!(solve_part2(&get_day().read_input()), "42");
+        assert_input_answer(&get_day(), Part::Two);
     }
 }
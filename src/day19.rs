@@ -1,57 +1,57 @@
-use crate::util::Day;
+use miette::{bail, Result};
+
+use crate::util::{parse, Answer, Day, Example, Params, Solution};
 
 const DAY_NR: u8 = 19;
 const PROBLEM_TITLE: &str = "Linen Layout";
 
 pub fn get_day() -> Day {
-    Day::new(DAY_NR, PROBLEM_TITLE, solve_part1, solve_part2)
+    Day::from_solution(DAY_NR, PROBLEM_TITLE, LinenLayout).with_examples(EXAMPLES)
 }
 
-fn solve_part1(input: &str) -> String {
-    let parts = input.split("\n\n").collect::<Vec<&str>>();
-
-    let towels: Vec<String> = parts[0].split(", ").map(|t| t.to_owned()).collect();
-    let designs: Vec<String> = parts[1].lines().map(|d| d.to_owned()).collect();
+struct LinenLayout;
 
-    designs
-        .iter()
-        .filter(|d| possible(d, &towels))
-        .count()
-        .to_string()
+struct Onsen {
+    towels: Vec<String>,
+    designs: Vec<String>,
+    // the number of ways to arrange each design, counted by the first part that needs them
+    ways: Option<Vec<usize>>,
 }
 
-fn solve_part2(input: &str) -> String {
-    let parts = input.split("\n\n").collect::<Vec<&str>>();
+impl Onsen {
+    fn ways(&mut self) -> &[usize] {
+        let (towels, designs) = (&self.towels, &self.designs);
+        self.ways
+            .get_or_insert_with(|| designs.iter().map(|d| ways(d, towels)).collect())
+    }
+}
 
-    let towels: Vec<String> = parts[0].split(", ").map(|t| t.to_owned()).collect();
-    let designs: Vec<String> = parts[1].lines().map(|d| d.to_owned()).collect();
+impl Solution for LinenLayout {
+    type Parsed = Onsen;
 
-    designs
-        .iter()
-        .map(|d| ways(d, &towels))
-        .sum::<usize>()
-        .to_string()
-}
+    fn parse(&self, input: &str) -> Result<Onsen> {
+        let sections = parse::sections(input);
+        let [towels, designs] = sections[..] else {
+            bail!("Expected towels and designs separated by a blank line.");
+        };
 
-fn possible(s: &String, l: &Vec<String>) -> bool {
-    let n = s.len();
-    let mut dp = vec![false; n + 1];
-    dp[0] = true;
+        Ok(Onsen {
+            towels: towels.list(',')?,
+            designs: designs.words()?,
+            ways: None,
+        })
+    }
 
-    for i in 1..=n {
-        for towel in l {
-            let towel_len = towel.len();
-            if i >= towel_len && &s[i - towel_len..i] == towel && dp[i - towel_len] {
-                dp[i] = true;
-                break;
-            }
-        }
+    fn part1(&self, onsen: &mut Onsen, _params: &Params) -> Result<Answer> {
+        Ok(onsen.ways().iter().filter(|&&n| n > 0).count().into())
     }
 
-    dp[n]
+    fn part2(&self, onsen: &mut Onsen, _params: &Params) -> Result<Answer> {
+        Ok(onsen.ways().iter().sum::<usize>().into())
+    }
 }
 
-fn ways(s: &String, l: &Vec<String>) -> usize {
+fn ways(s: &str, l: &[String]) -> usize {
     let n = s.len();
     let mut dp = vec![0; n + 1];
     dp[0] = 1;
@@ -68,12 +68,7 @@ fn ways(s: &String, l: &Vec<String>) -> usize {
     dp[n]
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    fn example() -> &'static str {
-        "r, wr, b, g, bwu, rb, gb, br
+const EXAMPLE: &str = "r, wr, b, g, bwu, rb, gb, br
 
 brwrr
 bggr
@@ -82,26 +77,33 @@ rrbgbr
 ubwu
 bwurrg
 brgr
-bbrgwb"
-    }
+bbrgwb";
+
+const EXAMPLES: &[Example] = &[Example::new("example", EXAMPLE).part1("6").part2("16")];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answers::assert_input_answer;
+    use crate::util::{assert_example_answers, Part};
 
     #[test]
     fn test_part1_with_examples() {
-        assert_eq!(solve_part1(example()), "6");
+        assert_example_answers(&get_day(), Part::One);
     }
 
     #[test]
     fn test_part1_with_input() {
-        assert_eq!(solve_part1(&get_day().read_input()), "293");
+        assert_input_answer(&get_day(), Part::One);
     }
 
     #[test]
     fn test_part2_with_examples() {
-        assert_eq!(solve_part2(example()), "16");
+        assert_example_answers(&get_day(), Part::Two);
     }
 
     #[test]
     fn test_part2_with_input() {
-        assert_eq!(solve_part2(&get_day().read_input()), "623924810770264");
+        assert_input_answer(&get_day(), Part::Two);
     }
 }
@@ -1,49 +1,54 @@
-use crate::util::Day;
+use log::debug;
+use miette::Result;
+
+use crate::util::{Answer, Day, Example, Params};
 
 const DAY_NR: u8 = XX;
 const PROBLEM_TITLE: &str = "TBD";
 
 pub fn get_day() -> Day {
-    Day::new(DAY_NR, PROBLEM_TITLE, solve_part1, solve_part2)
+    Day::new(DAY_NR, PROBLEM_TITLE, solve_part1, solve_part2).with_examples(EXAMPLES)
 }
 
-fn solve_part1(input: &str) -> String {
-    println!("{}", input);
+fn solve_part1(input: &str, _params: &Params) -> Result<Answer> {
+    debug!("{}", input);
 
-    "17".to_string()
+    Ok(Answer::Unsolved)
 }
 
-fn solve_part2(input: &str) -> String {
-    println!("{}", input);
+fn solve_part2(input: &str, _params: &Params) -> Result<Answer> {
+    debug!("{}", input);
 
-    "42".to_string()
+    Ok(Answer::Unsolved)
 }
 
+const EXAMPLE: &str = "";
+
+const EXAMPLES: &[Example] = &[Example::new("example", EXAMPLE)];
+
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    fn example() -> &'static str {
-        ""
-    }
+    use crate::answers::assert_input_answer;
+    use crate::util::{assert_example_answers, Part};
 
     #[test]
     fn test_part1_with_examples() {
-        assert_eq!(solve_part1(example()), "17");
+        assert_example_answers(&get_day(), Part::One);
     }
 
     #[test]
     fn test_part1_with_input() {
-        assert_eq!(solve_part1(&get_day().read_input()), "17");
+        assert_input_answer(&get_day(), Part::One);
     }
 
     #[test]
     fn test_part2_with_examples() {
-        assert_eq!(solve_part2(example()), "42");
+        assert_example_answers(&get_day(), Part::Two);
     }
 
     #[test]
     fn test_part2_with_input() {
-        assert_eq!(solve_part2(&get_day().read_input()), "42");
+        assert_input_answer(&get_day(), Part::Two);
     }
 }
@@ -0,0 +1,99 @@
+use crate::util::Day;
+use crate::{
+    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day13, day14,
+    day15, day16, day17, day18, day19,
+};
+
+pub const FIRST_DAY: u8 = 1;
+pub const LAST_DAY: u8 = 25;
+
+// All implemented days, ordered by day number. New days have to be added here.
+const REGISTRY: &[fn() -> Day] = &[
+    day01::get_day,
+    day02::get_day,
+    day03::get_day,
+    day04::get_day,
+    day05::get_day,
+    day06::get_day,
+    day07::get_day,
+    day08::get_day,
+    day09::get_day,
+    day10::get_day,
+    day11::get_day,
+    day13::get_day,
+    day14::get_day,
+    day15::get_day,
+    day16::get_day,
+    day17::get_day,
+    day18::get_day,
+    day19::get_day,
+];
+
+pub fn all() -> Vec<Day> {
+    REGISTRY.iter().map(|get_day| get_day()).collect()
+}
+
+pub fn get(number: u8) -> Option<Day> {
+    all().into_iter().find(|day| day.number() == number)
+}
+
+pub fn in_range(first: u8, last: u8) -> Vec<Day> {
+    all()
+        .into_iter()
+        .filter(|day| (first..=last).contains(&day.number()))
+        .collect()
+}
+
+pub fn missing() -> Vec<u8> {
+    let implemented = all().iter().map(|day| day.number()).collect::<Vec<u8>>();
+
+    (FIRST_DAY..=LAST_DAY)
+        .filter(|number| !implemented.contains(number))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_is_ordered_and_unique() {
+        let numbers = all().iter().map(|day| day.number()).collect::<Vec<u8>>();
+        assert!(numbers.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(numbers
+            .iter()
+            .all(|number| (FIRST_DAY..=LAST_DAY).contains(number)));
+    }
+
+    #[test]
+    fn test_missing_days() {
+        assert!(missing().contains(&12));
+        assert!(!missing().contains(&1));
+        assert_eq!(get(12).map(|day| day.number()), None);
+        assert_eq!(get(17).map(|day| day.number()), Some(17));
+    }
+
+    #[test]
+    fn test_example_names_are_unique() {
+        for day in all() {
+            let examples = day.examples();
+            for (i, example) in examples.iter().enumerate() {
+                assert!(
+                    examples[..i].iter().all(|other| other.name != example.name),
+                    "day {} has two examples named {}",
+                    day.number(),
+                    example.name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_range() {
+        let numbers = in_range(10, 14)
+            .iter()
+            .map(|day| day.number())
+            .collect::<Vec<u8>>();
+        assert_eq!(numbers, vec![10, 11, 13, 14]);
+    }
+}
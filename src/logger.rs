@@ -0,0 +1,56 @@
+use std::io::{self, Write};
+
+use log::{LevelFilter, Log, Metadata, Record};
+
+/// Writes the log records to stderr, so stdout only has the answers.
+struct StderrLogger;
+
+impl Log for StderrLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        // the module is enough to tell the days apart, e.g. `day08`
+        let target = record.target().rsplit("::").next().unwrap_or_default();
+        eprintln!("[{} {}] {}", record.level(), target, record.args());
+    }
+
+    fn flush(&self) {
+        let _ = io::stderr().flush();
+    }
+}
+
+static LOGGER: StderrLogger = StderrLogger;
+
+/// The log level for the number of `-v` flags: warnings only by default, `-v` adds the debug
+/// output of the solvers and `-vv` everything they trace.
+pub fn level_filter(verbosity: u8) -> LevelFilter {
+    match verbosity {
+        0 => LevelFilter::Warn,
+        1 => LevelFilter::Debug,
+        _ => LevelFilter::Trace,
+    }
+}
+
+pub fn init(verbosity: u8) {
+    // the logger can only be set once, a second call just changes the level
+    let _ = log::set_logger(&LOGGER);
+    log::set_max_level(level_filter(verbosity));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_level_filter() {
+        assert_eq!(level_filter(0), LevelFilter::Warn);
+        assert_eq!(level_filter(1), LevelFilter::Debug);
+        assert_eq!(level_filter(2), LevelFilter::Trace);
+        assert_eq!(level_filter(5), LevelFilter::Trace);
+    }
+}
@@ -0,0 +1,234 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use miette::{bail, miette, Result};
+
+use crate::util::input_dir;
+
+const TEMPLATE_FILE: &str = "dayXX template.rs";
+
+// rustfmt's maximum line width, the registered modules are wrapped the same way
+const MAX_WIDTH: usize = 100;
+
+/// The files written for a new day.
+#[derive(Debug, Default)]
+pub struct Scaffold {
+    pub created: Vec<PathBuf>,
+    pub changed: Vec<PathBuf>,
+}
+
+fn src_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("src")
+}
+
+fn module_name(number: u8) -> String {
+    format!("day{:0>2}", number)
+}
+
+fn read(path: &Path) -> Result<String> {
+    fs::read_to_string(path).map_err(|e| miette!("Failed to read {}: {}", path.display(), e))
+}
+
+fn write(path: &Path, content: &str) -> Result<()> {
+    fs::write(path, content).map_err(|e| miette!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Creates the module of a new day from the day template, registers it in `main.rs` and
+/// `days.rs` and creates an empty input file. An existing day is never overwritten.
+pub fn create_day(number: u8, title: &str) -> Result<Scaffold> {
+    let src = src_dir();
+    let module_path = src.join(format!("{}.rs", module_name(number)));
+    if module_path.exists() {
+        bail!("{} already exists.", module_path.display());
+    }
+
+    // everything is prepared before the first write, so a failure leaves the tree untouched
+    let main_path = src.join("main.rs");
+    let days_path = src.join("days.rs");
+    let module = fill_template(&read(&src.join(TEMPLATE_FILE))?, number, title)?;
+    let main = register_module(&read(&main_path)?, number)?;
+    let days = register_day(&read(&days_path)?, number)?;
+
+    let mut scaffold = Scaffold::default();
+    write(&module_path, &module)?;
+    scaffold.created.push(module_path);
+    write(&main_path, &main)?;
+    scaffold.changed.push(main_path);
+    write(&days_path, &days)?;
+    scaffold.changed.push(days_path);
+
+    let input_dir = input_dir();
+    let input_path = input_dir.join(format!("input_{:0>2}.txt", number));
+    if !input_path.exists() {
+        fs::create_dir_all(&input_dir)
+            .map_err(|e| miette!("Failed to create {}: {}", input_dir.display(), e))?;
+        write(&input_path, "")?;
+        scaffold.created.push(input_path);
+    }
+
+    Ok(scaffold)
+}
+
+fn fill_template(template: &str, number: u8, title: &str) -> Result<String> {
+    const DAY_NR: &str = "const DAY_NR: u8 = XX;";
+    const TITLE: &str = "const PROBLEM_TITLE: &str = \"TBD\";";
+
+    if !template.contains(DAY_NR) || !template.contains(TITLE) {
+        bail!("The day template has no `{}` or `{}`.", DAY_NR, TITLE);
+    }
+
+    // the title is written with debug formatting to get a valid string literal
+    Ok(template
+        .replace(DAY_NR, &format!("const DAY_NR: u8 = {};", number))
+        .replace(TITLE, &format!("const PROBLEM_TITLE: &str = {:?};", title)))
+}
+
+/// Adds `mod dayNN;` to the module declarations of `main.rs`.
+fn register_module(main: &str, number: u8) -> Result<String> {
+    let line = format!("mod {};", module_name(number));
+    insert_line(main, &line, |l| {
+        l.starts_with("mod day") && l.ends_with(';') && !l.starts_with("mod days")
+    })
+    .ok_or_else(|| miette!("Found no day modules in main.rs."))
+}
+
+/// Adds `dayNN` to the imports and `dayNN::get_day` to the registry of `days.rs`.
+fn register_day(days: &str, number: u8) -> Result<String> {
+    let module = module_name(number);
+    let days = insert_line(days, &format!("    {}::get_day,", module), |l| {
+        l.trim_start().starts_with("day") && l.ends_with("::get_day,")
+    })
+    .ok_or_else(|| miette!("Found no registered days in days.rs."))?;
+
+    let start = days
+        .find("use crate::{")
+        .ok_or_else(|| miette!("Found no `use crate::{{...}};` in days.rs."))?;
+    let end = start
+        + days[start..]
+            .find("};")
+            .ok_or_else(|| miette!("Found no end of `use crate::{{...}};` in days.rs."))?;
+
+    let mut modules = days[start + "use crate::{".len()..end]
+        .split(',')
+        .map(|module| module.trim())
+        .filter(|module| !module.is_empty())
+        .collect::<Vec<_>>();
+    modules.push(&module);
+    modules.sort();
+    modules.dedup();
+
+    Ok(format!(
+        "{}{}{}",
+        &days[..start],
+        format_use(&modules),
+        &days[end + "};".len()..]
+    ))
+}
+
+// Inserts the line in order among the lines matching `is_entry`. Returns None without such lines.
+fn insert_line(source: &str, line: &str, is_entry: impl Fn(&str) -> bool) -> Option<String> {
+    let mut lines = source.lines().collect::<Vec<_>>();
+    let entries = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, l)| is_entry(l))
+        .map(|(i, l)| (i, *l))
+        .collect::<Vec<_>>();
+
+    let (last, _) = *entries.last()?;
+    let index = entries
+        .iter()
+        .find(|(_, entry)| *entry >= line)
+        .map_or(last + 1, |&(i, _)| i);
+    if lines.get(index) != Some(&line) {
+        lines.insert(index, line);
+    }
+
+    let mut result = lines.join("\n");
+    if source.ends_with('\n') {
+        result.push('\n');
+    }
+    Some(result)
+}
+
+// Formats `use crate::{...};` like rustfmt: on one line if it fits, wrapped otherwise.
+fn format_use(modules: &[&str]) -> String {
+    let single_line = format!("use crate::{{{}}};", modules.join(", "));
+    if single_line.len() <= MAX_WIDTH {
+        return single_line;
+    }
+
+    let mut result = "use crate::{\n".to_string();
+    let mut line = String::new();
+    for module in modules {
+        if !line.is_empty() && 4 + line.len() + 1 + module.len() + 1 > MAX_WIDTH {
+            result.push_str(&format!("    {}\n", line));
+            line.clear();
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(module);
+        line.push(',');
+    }
+    result.push_str(&format!("    {}\n}};", line));
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fill_template() {
+        let template = read(&src_dir().join(TEMPLATE_FILE)).unwrap();
+        let module = fill_template(&template, 20, "Race \"Condition\"").unwrap();
+        assert!(module.contains("const DAY_NR: u8 = 20;"));
+        assert!(module.contains(r#"const PROBLEM_TITLE: &str = "Race \"Condition\"";"#));
+        assert!(fill_template("", 20, "Race Condition").is_err());
+    }
+
+    #[test]
+    fn test_register_module() {
+        let main = "mod cli;\nmod day01;\nmod day19;\nmod days;\nmod util;\n";
+        assert_eq!(
+            register_module(main, 12).unwrap(),
+            "mod cli;\nmod day01;\nmod day12;\nmod day19;\nmod days;\nmod util;\n"
+        );
+        assert_eq!(
+            register_module(main, 20).unwrap(),
+            "mod cli;\nmod day01;\nmod day19;\nmod day20;\nmod days;\nmod util;\n"
+        );
+        assert!(register_module("mod cli;\n", 20).is_err());
+    }
+
+    #[test]
+    fn test_register_day() {
+        let days = "use crate::util::Day;
+use crate::{day01, day19};
+
+const REGISTRY: &[fn() -> Day] = &[
+    day01::get_day,
+    day19::get_day,
+];
+";
+        assert_eq!(
+            register_day(days, 12).unwrap(),
+            "use crate::util::Day;
+use crate::{day01, day12, day19};
+
+const REGISTRY: &[fn() -> Day] = &[
+    day01::get_day,
+    day12::get_day,
+    day19::get_day,
+];
+"
+        );
+
+        // the registry of this crate is wrapped like rustfmt does it
+        let days = read(&src_dir().join("days.rs")).unwrap();
+        let registered = register_day(&days, 20).unwrap();
+        assert!(registered.contains("    day20::get_day,\n"));
+        assert!(registered.lines().all(|line| line.len() <= MAX_WIDTH));
+    }
+}
@@ -1,36 +1,635 @@
 use core::fmt;
+use std::any::Any;
+use std::env;
 use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
-pub type Solver = fn(&str) -> String;
+use solution::{DynSolution, FnSolution};
+
+use miette::{miette, NamedSource, Report, Result};
+
+pub mod export;
+pub mod grid;
+pub mod parse;
+pub mod point;
+pub mod record;
+pub mod render;
+pub mod solution;
+
+pub use grid::Grid;
+pub use point::{manhattan, Direction, Point};
+pub use solution::Solution;
+
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+pub type Solver = fn(&str, &Params) -> Result<Answer>;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Int(i128),
+    Text(String),
+    #[allow(dead_code)] // returned by the parts of a new day until they are solved
+    Unsolved,
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+            Answer::Unsolved => write!(f, "(unsolved)"),
+        }
+    }
+}
+
+macro_rules! impl_answer_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::Int(n as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_answer_from_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, i128);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
+// Compares solved answers by their printed value, so tests can check against plain strings.
+impl PartialEq<&str> for Answer {
+    fn eq(&self, other: &&str) -> bool {
+        match self {
+            Answer::Int(n) => n.to_string() == *other,
+            Answer::Text(s) => s == other,
+            Answer::Unsolved => false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, clap::ValueEnum)]
+pub enum Part {
+    #[value(name = "1")]
+    One,
+    #[value(name = "2")]
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "Part 1"),
+            Part::Two => write!(f, "Part 2"),
+        }
+    }
+}
+
+/// A tunable parameter of a day, e.g. the size of the grid, with its defaults for the real input.
+#[derive(Clone, Copy, Debug)]
+pub struct Param {
+    pub name: &'static str,
+    pub part1: i64,
+    pub part2: i64,
+}
+
+impl Param {
+    pub const fn new(name: &'static str, default: i64) -> Self {
+        Param {
+            name,
+            part1: default,
+            part2: default,
+        }
+    }
+
+    /// Sets a different default for part 2.
+    pub const fn part2(mut self, default: i64) -> Self {
+        self.part2 = default;
+        self
+    }
+
+    pub fn default_for(&self, part: Part) -> i64 {
+        match part {
+            Part::One => self.part1,
+            Part::Two => self.part2,
+        }
+    }
+}
+
+/// Values of the parameters of a day. The solvers get the defaults of the part with the values of
+/// the example or the command line applied.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Params {
+    values: Vec<(String, i64)>,
+}
+
+impl Params {
+    pub fn new(values: &[(&str, i64)]) -> Self {
+        let mut params = Params::default();
+        for &(name, value) in values {
+            params.set(name, value);
+        }
+        params
+    }
+
+    /// Sets the value of a parameter, replacing an earlier value.
+    pub fn set(&mut self, name: &str, value: i64) {
+        match self.values.iter_mut().find(|(n, _)| n == name) {
+            Some((_, v)) => *v = value,
+            None => self.values.push((name.to_string(), value)),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, i64)> {
+        self.values
+            .iter()
+            .map(|(name, value)| (name.as_str(), *value))
+    }
+
+    /// The value of a parameter, converted to the type the solver works with.
+    pub fn get<T: TryFrom<i64>>(&self, name: &str) -> Result<T> {
+        let value = self
+            .iter()
+            .find(|&(n, _)| n == name)
+            .map(|(_, value)| value)
+            .ok_or_else(|| miette!("Parameter {} is not declared.", name))?;
+
+        T::try_from(value).map_err(|_| miette!("Parameter {} is out of range: {}", name, value))
+    }
+
+    /// The value of a parameter that has to be greater than 0, like the size of a grid.
+    pub fn get_positive<T: TryFrom<i64>>(&self, name: &str) -> Result<T> {
+        let value: i64 = self.get(name)?;
+        if value <= 0 {
+            return Err(miette!(
+                "Parameter {} has to be greater than 0: {}",
+                name,
+                value
+            ));
+        }
+        self.get(name)
+    }
+}
+
+impl fmt::Display for Params {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let values = self
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect::<Vec<_>>();
+        write!(f, "{}", values.join(", "))
+    }
+}
+
+/// An example from the puzzle description with the answers given there.
+#[derive(Clone, Copy, Debug)]
+pub struct Example {
+    pub name: &'static str,
+    pub input: &'static str,
+    pub part1: Option<&'static str>,
+    pub part2: Option<&'static str>,
+    pub params: &'static [(&'static str, i64)],
+}
+
+impl Example {
+    pub const fn new(name: &'static str, input: &'static str) -> Self {
+        Example {
+            name,
+            input,
+            part1: None,
+            part2: None,
+            params: &[],
+        }
+    }
+
+    pub const fn part1(mut self, answer: &'static str) -> Self {
+        self.part1 = Some(answer);
+        self
+    }
+
+    pub const fn part2(mut self, answer: &'static str) -> Self {
+        self.part2 = Some(answer);
+        self
+    }
+
+    pub const fn params(mut self, params: &'static [(&'static str, i64)]) -> Self {
+        self.params = params;
+        self
+    }
+
+    pub fn answer(&self, part: Part) -> Option<&'static str> {
+        match part {
+            Part::One => self.part1,
+            Part::Two => self.part2,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// `input_NN.txt` in the input directory
+    #[default]
+    Default,
+    File(PathBuf),
+    Stdin,
+    /// an example of the day, selected by number (starting at 1) or name
+    Example(String),
+}
+
+impl InputSource {
+    /// Parses the value of an `--input` argument, `-` stands for stdin.
+    pub fn from_arg(arg: &str) -> Self {
+        match arg {
+            "-" => InputSource::Stdin,
+            path => InputSource::File(PathBuf::from(path)),
+        }
+    }
+}
+
+/// The directory with the puzzle inputs: `$AOC_INPUT_DIR` if set, `input/` in the crate root
+/// otherwise. This does not depend on the working directory, so tests can run from anywhere.
+pub fn input_dir() -> PathBuf {
+    match env::var_os(INPUT_DIR_VAR) {
+        Some(dir) => PathBuf::from(dir),
+        None => Path::new(env!("CARGO_MANIFEST_DIR")).join("input"),
+    }
+}
+
+/// A puzzle input in the input directory: `input_NN.txt` or the input of a teammate, named
+/// `input_NN.<name>.txt`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NamedInput {
+    /// None for `input_NN.txt`
+    pub name: Option<String>,
+    pub path: PathBuf,
+}
+
+impl fmt::Display for NamedInput {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "{}", name),
+            None => write!(f, "default"),
+        }
+    }
+}
+
+pub struct PartResult {
+    pub part: Part,
+    pub answer: Result<Answer>,
+    pub time: Duration,
+}
+
+/// The results of the parts with the time it took to parse the input they share.
+pub struct DayResult {
+    /// None for days that parse the input in their parts
+    pub parse_time: Option<Duration>,
+    pub parts: Vec<PartResult>,
+}
+
+impl DayResult {
+    /// Parsing and solving together.
+    pub fn time(&self) -> Duration {
+        self.parse_time.unwrap_or_default()
+            + self.parts.iter().map(|part| part.time).sum::<Duration>()
+    }
+}
 
 pub struct Day {
     number: u8,
     title: String,
-    solver_part1: Solver,
-    solver_part2: Solver,
+    solution: Box<dyn DynSolution>,
+    examples: &'static [Example],
+    params: &'static [Param],
 }
 
 impl Day {
     pub fn new(number: u8, title: &str, solver1: Solver, solver2: Solver) -> Self {
+        let solution = FnSolution {
+            part1: solver1,
+            part2: solver2,
+        };
+        Self::from_solution(number, title, solution)
+    }
+
+    /// A day with a [`Solution`] that parses the input once for both parts.
+    pub fn from_solution(number: u8, title: &str, solution: impl Solution + 'static) -> Self {
         Self {
             number,
             title: String::from(title),
-            solver_part1: solver1,
-            solver_part2: solver2,
+            solution: Box::new(solution),
+            examples: &[],
+            params: &[],
+        }
+    }
+
+    pub fn with_examples(mut self, examples: &'static [Example]) -> Self {
+        self.examples = examples;
+        self
+    }
+
+    pub fn with_params(mut self, params: &'static [Param]) -> Self {
+        self.params = params;
+        self
+    }
+
+    pub fn number(&self) -> u8 {
+        self.number
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn examples(&self) -> &[Example] {
+        self.examples
+    }
+
+    pub fn params(&self) -> &[Param] {
+        self.params
+    }
+
+    /// The parameters for a part: the declared defaults with the given values applied.
+    pub fn params_for(&self, part: Part, values: &Params) -> Result<Params> {
+        let mut params = Params::default();
+        for param in self.params {
+            params.set(param.name, param.default_for(part));
+        }
+
+        for (name, value) in values.iter() {
+            if !self.params.iter().any(|param| param.name == name) {
+                let names = self
+                    .params
+                    .iter()
+                    .map(|param| param.name)
+                    .collect::<Vec<_>>();
+                return Err(miette!(
+                    help = match names.is_empty() {
+                        true => "This day has no parameters.".to_string(),
+                        false => format!("Available parameters: {}", names.join(", ")),
+                    },
+                    "Day {} has no parameter '{}'.",
+                    self.number,
+                    name
+                ));
+            }
+            params.set(name, value);
         }
+
+        Ok(params)
     }
 
-    pub fn solve(&self) {
+    /// Selects an example by its number, starting at 1, or by its name.
+    pub fn example(&self, selection: &str) -> Result<&Example> {
+        let example = match selection.parse::<usize>() {
+            Ok(n) => n.checked_sub(1).and_then(|i| self.examples.get(i)),
+            Err(_) => self.examples.iter().find(|ex| ex.name == selection),
+        };
+
+        example.ok_or_else(|| {
+            let names = self
+                .examples
+                .iter()
+                .enumerate()
+                .map(|(i, ex)| format!("{} ({})", i + 1, ex.name))
+                .collect::<Vec<_>>();
+            miette!(
+                help = match names.is_empty() {
+                    true => "This day has no examples.".to_string(),
+                    false => format!("Available examples: {}", names.join(", ")),
+                },
+                "Day {} has no example '{}'.",
+                self.number,
+                selection
+            )
+        })
+    }
+
+    /// Solves the parts for the input, the given parameter values override the defaults and the
+    /// values of an example. The input is parsed once and the parts share the parsed input.
+    pub fn solve(
+        &self,
+        parts: &[Part],
+        source: &InputSource,
+        overrides: &Params,
+    ) -> Result<DayResult> {
         println!("{}", self);
-        let input = self.read_input();
-        println!("Part 1: {}", (self.solver_part1)(&input));
-        println!("Part 2: {}", (self.solver_part2)(&input));
+        let input = self.read_input_from(source)?;
+        let (mut params, example) = match source {
+            InputSource::Example(selection) => {
+                let example = self.example(selection)?;
+                println!("Example: {}", example.name);
+                (Params::new(example.params), Some(example))
+            }
+            _ => (Params::default(), None),
+        };
+        for (name, value) in overrides.iter() {
+            params.set(name, value);
+        }
+        if !params.is_empty() {
+            // fail before solving anything on an unknown parameter
+            self.params_for(Part::One, &params)?;
+            println!("Parameters: {}", params);
+        }
+
+        export::set_day(self.number);
+        let start = Instant::now();
+        let parsed = self.solution.parse(&input);
+        let elapsed = start.elapsed();
+        let parse_time = self.solution.parses().then_some(elapsed);
+        let mut parsed = match parsed {
+            Ok(parsed) => {
+                if let Some(time) = parse_time {
+                    println!("Parse: ({:.2?})", time);
+                }
+                parsed
+            }
+            Err(err) => {
+                // reported once, the parts only refer to it
+                println!("Parse: failed ({:.2?})", elapsed);
+                print_error(&with_input(err, &self.input_name(source), &input));
+                let parts = parts
+                    .iter()
+                    .map(|&part| PartResult {
+                        part,
+                        answer: Err(miette!("The input could not be parsed.")),
+                        time: Duration::ZERO,
+                    })
+                    .collect();
+                return Ok(DayResult { parse_time, parts });
+            }
+        };
+
+        let parts = parts
+            .iter()
+            .map(|&part| {
+                let start = Instant::now();
+                let answer = self
+                    .params_for(part, &params)
+                    .and_then(|params| self.solution.solve(part, &mut *parsed, &params))
+                    .map_err(|err| with_input(err, &self.input_name(source), &input));
+                let time = start.elapsed();
+
+                let expected = example.and_then(|example| example.answer(part));
+                match &answer {
+                    Ok(answer) => match expected {
+                        Some(expected) if answer != &expected => {
+                            println!("{}: {}, expected {} ({:.2?})", part, answer, expected, time)
+                        }
+                        _ => println!("{}: {} ({:.2?})", part, answer, time),
+                    },
+                    Err(err) => {
+                        println!("{}: failed ({:.2?})", part, time);
+                        print_error(err);
+                    }
+                }
+                PartResult { part, answer, time }
+            })
+            .collect();
+        Ok(DayResult { parse_time, parts })
     }
 
-    pub fn read_input(&self) -> String {
-        let path = fs::canonicalize(format!("input/input_{:0>2}.txt", self.number))
-            .expect("Problem with input file path:");
-        fs::read_to_string(path).expect("Failed to read input file:")
+    /// Solves a part for the real puzzle input with the default parameters.
+    pub fn solve_part(&self, part: Part, input: &str) -> Result<Answer> {
+        self.solve_part_with(part, input, &Params::default())
+    }
+
+    /// Parses the input and solves a part, the other part is not run.
+    pub fn solve_part_with(&self, part: Part, input: &str, values: &Params) -> Result<Answer> {
+        self.params_for(part, values)?;
+        let mut parsed = self.parse(input)?;
+        self.solve_parsed(part, &mut *parsed, values)
+    }
+
+    /// Whether the day parses its input before the parts, days of plain solver functions don't.
+    pub fn parses(&self) -> bool {
+        self.solution.parses()
+    }
+
+    /// Parses the input for [`Day::solve_parsed`].
+    pub fn parse(&self, input: &str) -> Result<Box<dyn Any>> {
+        self.solution.parse(input)
+    }
+
+    /// Solves a part for an input that was parsed by [`Day::parse`].
+    pub fn solve_parsed(
+        &self,
+        part: Part,
+        parsed: &mut dyn Any,
+        values: &Params,
+    ) -> Result<Answer> {
+        let params = self.params_for(part, values)?;
+        self.solution.solve(part, parsed, &params)
+    }
+
+    pub fn input_path(&self) -> PathBuf {
+        input_dir().join(format!("input_{:0>2}.txt", self.number))
+    }
+
+    /// All inputs of the day in the input directory, the default input first and the named
+    /// inputs ordered by name.
+    pub fn inputs(&self) -> Result<Vec<NamedInput>> {
+        let dir = input_dir();
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(miette!("Failed to read {}: {}", dir.display(), e)),
+        };
+
+        let prefix = format!("input_{:0>2}", self.number);
+        let mut inputs = vec![];
+        for entry in entries {
+            let path = entry
+                .map_err(|e| miette!("Failed to read {}: {}", dir.display(), e))?
+                .path();
+            let Some(rest) = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_prefix(&prefix))
+                .and_then(|name| name.strip_suffix(".txt"))
+            else {
+                continue;
+            };
+
+            let name = match rest.strip_prefix('.') {
+                Some(name) if !name.is_empty() => Some(name.to_string()),
+                None if rest.is_empty() => None,
+                _ => continue,
+            };
+            inputs.push(NamedInput { name, path });
+        }
+        inputs.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(inputs)
+    }
+
+    /// The name of the input in error reports: the path of an input file, `stdin` or the example.
+    pub fn input_name(&self, source: &InputSource) -> String {
+        match source {
+            InputSource::Default => self.input_path().display().to_string(),
+            InputSource::File(path) => path.display().to_string(),
+            InputSource::Stdin => "stdin".to_string(),
+            InputSource::Example(selection) => match self.example(selection) {
+                Ok(example) => format!("example {}", example.name),
+                Err(_) => format!("example {}", selection),
+            },
+        }
+    }
+
+    /// Reads the input and normalizes it with [`normalize_input`], so the solvers get the same
+    /// input whatever editor it was saved with.
+    pub fn read_input_from(&self, source: &InputSource) -> Result<String> {
+        let input = self.read_raw_input(source)?;
+        Ok(normalize_input(&input))
+    }
+
+    fn read_raw_input(&self, source: &InputSource) -> Result<String> {
+        let path = match source {
+            InputSource::Default => self.input_path(),
+            InputSource::File(path) => path.clone(),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(|e| miette!("Failed to read input from stdin: {}", e))?;
+                return Ok(input);
+            }
+            InputSource::Example(selection) => {
+                return Ok(self.example(selection)?.input.to_string());
+            }
+        };
+
+        fs::read_to_string(&path).map_err(|e| {
+            miette!(
+                help = format!(
+                    "Pass the input with --input <path> or set {} to the input directory.",
+                    INPUT_DIR_VAR
+                ),
+                "Failed to read input file {}: {}",
+                path.display(),
+                e
+            )
+        })
     }
 }
 
@@ -39,3 +638,202 @@ impl fmt::Display for Day {
         write!(f, "--- Day {}: {} ---", self.number, self.title)
     }
 }
+
+/// Normalizes the line endings to `\n` and removes a byte order mark, the trailing whitespace of
+/// every line and the empty lines at the end.
+pub fn normalize_input(input: &str) -> String {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+    let input = input.replace("\r\n", "\n").replace('\r', "\n");
+
+    let mut normalized = input
+        .lines()
+        .map(|line| line.trim_end())
+        .collect::<Vec<_>>()
+        .join("\n");
+    normalized.truncate(normalized.trim_end().len());
+    normalized
+}
+
+/// Attaches the input to the error of a solver, so the report shows the lines a parse error
+/// points at.
+pub fn with_input(err: Report, name: &str, input: &str) -> Report {
+    err.with_source_code(NamedSource::new(name, input.to_string()))
+}
+
+/// Prints the error with its causes, labeled source lines and help.
+pub fn print_error(err: &Report) {
+    eprintln!("{:?}", err);
+}
+
+/// Checks the answers of all examples of the day that have an expected answer for the part.
+#[cfg(test)]
+pub fn assert_example_answers(day: &Day, part: Part) {
+    let examples = day
+        .examples()
+        .iter()
+        .filter_map(|example| example.answer(part).map(|expected| (example, expected)))
+        .collect::<Vec<_>>();
+    if examples.is_empty() {
+        eprintln!(
+            "Day {} {}: no example answers, skipped.",
+            day.number(),
+            part
+        );
+    }
+
+    for (example, expected) in examples {
+        let answer = day
+            .solve_part_with(part, example.input, &Params::new(example.params))
+            .unwrap();
+        assert_eq!(answer, expected, "example {}", example.name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer_conversions() {
+        assert_eq!(Answer::from(42u64), Answer::Int(42));
+        assert_eq!(Answer::from(-3i32), Answer::Int(-3));
+        assert_eq!(Answer::from("6,1"), Answer::Text("6,1".to_string()));
+        assert_eq!(Answer::Unsolved.to_string(), "(unsolved)");
+    }
+
+    #[test]
+    fn test_answer_compares_with_str() {
+        assert_eq!(Answer::Int(1928), "1928");
+        assert_eq!(Answer::from("4,6,3"), "4,6,3");
+        assert_ne!(Answer::Unsolved, "(unsolved)");
+    }
+
+    #[test]
+    fn test_input_source() {
+        assert_eq!(InputSource::from_arg("-"), InputSource::Stdin);
+        assert_eq!(
+            InputSource::from_arg("in.txt"),
+            InputSource::File(PathBuf::from("in.txt"))
+        );
+
+        let day = Day::new(
+            25,
+            "Test",
+            |_, _| Ok(Answer::Unsolved),
+            |_, _| Ok(Answer::Unsolved),
+        );
+        let source = InputSource::File(PathBuf::from("/does/not/exist.txt"));
+        let err = day.read_input_from(&source).unwrap_err();
+        assert!(err.to_string().contains("/does/not/exist.txt"));
+    }
+
+    #[test]
+    fn test_normalize_input() {
+        let unix = "2333133121414131402\n\n  indented\nlast";
+        let windows = "\u{feff}2333133121414131402 \r\n\r\n  indented\t\r\nlast\r\n\r\n";
+        assert_eq!(normalize_input(unix), unix);
+        assert_eq!(normalize_input(windows), unix);
+        assert_eq!(normalize_input("a\rb\n"), "a\nb");
+        assert_eq!(normalize_input(" \n \n"), "");
+    }
+
+    #[test]
+    fn test_examples() {
+        const EXAMPLES: &[Example] = &[
+            Example::new("small", "1 2").part1("3"),
+            Example::new("larger", "1 2 3")
+                .part1("6")
+                .part2("10")
+                .params(&[("factor", 2)]),
+        ];
+        const PARAMS: &[Param] = &[Param::new("factor", 1)];
+        let day = Day::new(
+            25,
+            "Test",
+            |input, _| Ok(input.split(' ').count().into()),
+            |input, params| Ok((input.len() as i64 * params.get::<i64>("factor")?).into()),
+        )
+        .with_examples(EXAMPLES)
+        .with_params(PARAMS);
+
+        assert_eq!(day.example("1").unwrap().name, "small");
+        assert_eq!(day.example("larger").unwrap().input, "1 2 3");
+        assert!(day.example("0").is_err());
+        assert!(day.example("3").is_err());
+        assert!(day.example("tiny").is_err());
+
+        let source = InputSource::Example("2".to_string());
+        assert_eq!(day.read_input_from(&source).unwrap(), "1 2 3");
+        let results = day
+            .solve(&Part::BOTH, &source, &Params::default())
+            .unwrap()
+            .parts;
+        assert_eq!(results[0].answer.as_ref().unwrap(), &Answer::Int(3));
+        assert_eq!(results[1].answer.as_ref().unwrap(), &Answer::Int(10));
+    }
+
+    #[test]
+    fn test_parse_failure() {
+        struct Failing;
+
+        impl Solution for Failing {
+            type Parsed = ();
+
+            fn parse(&self, input: &str) -> Result<()> {
+                Err(miette!("cannot parse {}", input))
+            }
+
+            fn part1(&self, _: &mut (), _: &Params) -> Result<Answer> {
+                Ok(Answer::Unsolved)
+            }
+
+            fn part2(&self, _: &mut (), _: &Params) -> Result<Answer> {
+                Ok(Answer::Unsolved)
+            }
+        }
+
+        const EXAMPLES: &[Example] = &[Example::new("example", "1 2")];
+        let day = Day::from_solution(25, "Test", Failing).with_examples(EXAMPLES);
+        let source = InputSource::Example("1".to_string());
+        let result = day.solve(&Part::BOTH, &source, &Params::default()).unwrap();
+        assert_eq!(result.parts.len(), 2);
+        assert!(result.parts.iter().all(|part| part.answer.is_err()));
+        assert!(day.solve_part(Part::One, "1 2").is_err());
+    }
+
+    #[test]
+    fn test_params() {
+        const PARAMS: &[Param] = &[Param::new("size", 71), Param::new("blinks", 25).part2(75)];
+        let day = Day::new(
+            25,
+            "Test",
+            |_, params| Ok(params.get::<u32>("blinks")?.into()),
+            |_, params| Ok(params.get::<u8>("size")?.into()),
+        )
+        .with_params(PARAMS);
+
+        assert_eq!(day.solve_part(Part::One, "").unwrap(), Answer::Int(25));
+        assert_eq!(day.solve_part(Part::Two, "").unwrap(), Answer::Int(71));
+
+        let params = day
+            .params_for(Part::Two, &Params::new(&[("size", 7)]))
+            .unwrap();
+        assert_eq!(params.to_string(), "size=7, blinks=75");
+
+        let overrides = Params::new(&[("size", 1000)]);
+        assert!(day.solve_part_with(Part::Two, "", &overrides).is_err());
+        assert!(day
+            .params_for(Part::One, &Params::new(&[("width", 7)]))
+            .is_err());
+        assert!(Params::default().get::<u8>("size").is_err());
+        assert!(Params::new(&[("size", 0)])
+            .get_positive::<u8>("size")
+            .is_err());
+        assert_eq!(
+            Params::new(&[("size", 7)])
+                .get_positive::<u8>("size")
+                .unwrap(),
+            7
+        );
+    }
+}
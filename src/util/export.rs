@@ -0,0 +1,279 @@
+use std::fmt::Write as _;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use miette::{bail, miette, Report, Result};
+
+use super::point::Point;
+use super::render::{Canvas, Render, Style};
+
+// pixels per cell in PPM and PNG images, SVG images are scalable anyway
+const SCALE: usize = 4;
+const SVG_CELL: usize = 8;
+
+// colours of the paths in SVG images, so overlapping paths can be told apart
+const PATH_COLOURS: [&str; 6] = [
+    "#5ae65a", "#e6a23c", "#3cb4e6", "#e65ab4", "#e6e65a", "#a05ae6",
+];
+
+// the CLI sets the target before the days are solved, the solvers add the images
+static EXPORT: Mutex<Option<Export>> = Mutex::new(None);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Ppm,
+    Png,
+    Svg,
+}
+
+impl Format {
+    pub fn from_path(path: &Path) -> Result<Self> {
+        let ext = path.extension().and_then(|ext| ext.to_str());
+        match ext.map(|ext| ext.to_ascii_lowercase()).as_deref() {
+            Some("ppm") => Ok(Format::Ppm),
+            Some("png") => Ok(Format::Png),
+            Some("svg") => Ok(Format::Svg),
+            _ => bail!(
+                help = "Use a path ending with .ppm, .png or .svg.",
+                "Unknown image format of {}.",
+                path.display()
+            ),
+        }
+    }
+}
+
+struct Export {
+    target: PathBuf,
+    format: Format,
+    // the day that is solved, it is part of the image names
+    day: Option<u8>,
+    written: Vec<PathBuf>,
+    // the first write error, reported when the export is finished
+    error: Option<Report>,
+}
+
+/// Turns on the export of images, they are written next to `target` with the day and the name
+/// of the image added to its file name, e.g. `maze.svg` becomes `maze-day16-best-path.svg`.
+pub fn start(target: &Path) -> Result<()> {
+    let format = Format::from_path(target)?;
+    check_writable(target)?;
+    *EXPORT.lock().unwrap() = Some(Export {
+        target: target.to_path_buf(),
+        format,
+        day: None,
+        written: vec![],
+        error: None,
+    });
+    Ok(())
+}
+
+// fails early on a missing or read-only directory instead of on the first image
+fn check_writable(target: &Path) -> Result<()> {
+    let dir = match target.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let probe = dir.join(format!(".export-check-{}", std::process::id()));
+    File::create(&probe)
+        .and_then(|_| fs::remove_file(&probe))
+        .map_err(|e| {
+            miette!(
+                help = "Export to a directory that exists and can be written.",
+                "Cannot write images to {}: {}",
+                dir.display(),
+                e
+            )
+        })
+}
+
+/// Sets the day whose images are exported next.
+pub fn set_day(day: u8) {
+    if let Some(export) = EXPORT.lock().unwrap().as_mut() {
+        export.day = Some(day);
+    }
+}
+
+/// Exports the state with the paths drawn over it if the export is turned on. A failed write
+/// does not fail the solver, the first error is reported by [`finish`].
+pub fn image(name: &str, state: &impl Render, paths: &[Vec<Point>]) {
+    let mut export = EXPORT.lock().unwrap();
+    let Some(export) = export.as_mut() else {
+        return;
+    };
+    if export.error.is_some() {
+        return;
+    }
+
+    let stem = export
+        .target
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("image");
+    let ext = export
+        .target
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or_default();
+    let name = match export.day {
+        Some(day) => format!("{}-day{:0>2}-{}.{}", stem, day, name, ext),
+        None => format!("{}-{}.{}", stem, name, ext),
+    };
+    let path = export.target.with_file_name(name);
+
+    match write_image(&path, export.format, &state.canvas(), paths) {
+        Ok(()) => export.written.push(path),
+        Err(e) => export.error = Some(e),
+    }
+}
+
+/// Turns the export off and returns the paths of the written images.
+pub fn finish() -> Result<Vec<PathBuf>> {
+    let Some(export) = EXPORT.lock().unwrap().take() else {
+        return Ok(vec![]);
+    };
+    match export.error {
+        Some(e) => Err(e),
+        None => Ok(export.written),
+    }
+}
+
+pub fn write_image(
+    path: &Path,
+    format: Format,
+    canvas: &Canvas,
+    paths: &[Vec<Point>],
+) -> Result<()> {
+    let err = |e: &dyn std::fmt::Display| miette!("Failed to write {}: {}", path.display(), e);
+    match format {
+        Format::Ppm => {
+            let (width, height, pixels) = raster(canvas, paths);
+            let mut ppm = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+            ppm.extend(pixels);
+            fs::write(path, ppm).map_err(|e| err(&e))
+        }
+        Format::Png => {
+            let (width, height, pixels) = raster(canvas, paths);
+            let file = File::create(path).map_err(|e| err(&e))?;
+            let mut encoder = png::Encoder::new(BufWriter::new(file), width as u32, height as u32);
+            encoder.set_color(png::ColorType::Rgb);
+            encoder.set_depth(png::BitDepth::Eight);
+            let mut writer = encoder.write_header().map_err(|e| err(&e))?;
+            writer.write_image_data(&pixels).map_err(|e| err(&e))
+        }
+        Format::Svg => {
+            let mut file = File::create(path).map_err(|e| err(&e))?;
+            file.write_all(svg(canvas, paths).as_bytes())
+                .map_err(|e| err(&e))
+        }
+    }
+}
+
+// RGB pixels with SCALE x SCALE pixels per cell, the cells of the paths in the path colour
+fn raster(canvas: &Canvas, paths: &[Vec<Point>]) -> (usize, usize, Vec<u8>) {
+    let (width, height) = (canvas.width() * SCALE, canvas.height() * SCALE);
+    let mut colours = canvas
+        .iter()
+        .map(|(_, cell)| cell.style.rgb())
+        .collect::<Vec<_>>();
+    for &pos in paths.iter().flatten() {
+        if pos.x >= 0 && pos.y >= 0 && (pos.x as usize) < canvas.width() {
+            if let Some(colour) = colours.get_mut(pos.y as usize * canvas.width() + pos.x as usize)
+            {
+                *colour = Style::Path.rgb();
+            }
+        }
+    }
+
+    let mut pixels = Vec::with_capacity(width * height * 3);
+    for y in 0..height {
+        for x in 0..width {
+            let cell = (y / SCALE) * canvas.width() + x / SCALE;
+            pixels.extend(colours[cell]);
+        }
+    }
+    (width, height, pixels)
+}
+
+// a rectangle per cell and a polyline through the cell centres per path
+fn svg(canvas: &Canvas, paths: &[Vec<Point>]) -> String {
+    let (width, height) = (canvas.width(), canvas.height());
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">\n",
+        width * SVG_CELL,
+        height * SVG_CELL,
+        width,
+        height
+    );
+    for (pos, cell) in canvas.iter() {
+        let [r, g, b] = cell.style.rgb();
+        let _ = writeln!(
+            svg,
+            "<rect x=\"{}\" y=\"{}\" width=\"1\" height=\"1\" fill=\"#{:02x}{:02x}{:02x}\"/>",
+            pos.x, pos.y, r, g, b
+        );
+    }
+    for (i, path) in paths.iter().enumerate() {
+        let points = path
+            .iter()
+            .map(|pos| format!("{}.5,{}.5", pos.x, pos.y))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let _ = writeln!(
+            svg,
+            "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"0.3\" stroke-opacity=\"0.8\" stroke-linejoin=\"round\"/>",
+            points,
+            PATH_COLOURS[i % PATH_COLOURS.len()]
+        );
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::Grid;
+
+    fn canvas() -> Canvas {
+        Grid::parse("#S.\n#.E", |ch| ch).unwrap().canvas()
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!(
+            Format::from_path(Path::new("tree.PNG")).unwrap(),
+            Format::Png
+        );
+        assert_eq!(
+            Format::from_path(Path::new("out/tree.ppm")).unwrap(),
+            Format::Ppm
+        );
+        assert_eq!(
+            Format::from_path(Path::new("maze.svg")).unwrap(),
+            Format::Svg
+        );
+        assert!(Format::from_path(Path::new("maze")).is_err());
+    }
+
+    #[test]
+    fn test_raster() {
+        let path = vec![Point::new(1, 0), Point::new(1, 1)];
+        let (width, height, pixels) = raster(&canvas(), &[path]);
+        assert_eq!((width, height), (3 * SCALE, 2 * SCALE));
+        assert_eq!(pixels.len(), width * height * 3);
+        assert_eq!(pixels[..3], Style::Wall.rgb());
+        // the top left pixel of the cell (1, 1) on the path
+        let i = (SCALE * width + SCALE) * 3;
+        assert_eq!(pixels[i..i + 3], Style::Path.rgb());
+    }
+
+    #[test]
+    fn test_svg() {
+        let svg = svg(&canvas(), &[vec![Point::new(1, 0), Point::new(2, 1)]]);
+        assert!(svg.starts_with("<svg "));
+        assert_eq!(svg.matches("<rect ").count(), 6);
+        assert!(svg.contains("points=\"1.5,0.5 2.5,1.5\""));
+    }
+}
@@ -0,0 +1,274 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use miette::Result;
+
+use super::parse::Span;
+use super::point::{Direction, Point};
+
+const NEIGHBOURS8: [Point; 8] = [
+    Point::new(0, -1),
+    Point::new(1, -1),
+    Point::new(1, 0),
+    Point::new(1, 1),
+    Point::new(0, 1),
+    Point::new(-1, 1),
+    Point::new(-1, 0),
+    Point::new(-1, -1),
+];
+
+/// A rectangular grid of cells, e.g. a map of the puzzle input. Positions are `Point`s with `x`
+/// as the column and `y` as the row, `(0, 0)` is the top left cell.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, cell: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![cell; width * height],
+        }
+    }
+
+    /// Parses a grid with one line per row, mapping every character to a cell.
+    pub fn parse<'a>(input: impl Into<Span<'a>>, mut cell: impl FnMut(char) -> T) -> Result<Self> {
+        Grid::try_parse(input, |ch| Ok(cell(ch)))
+    }
+
+    /// Parses a grid like [`Grid::parse`] with a cell mapping that can reject characters. The
+    /// error of a rejected character points at its position, the help of the error is kept.
+    pub fn try_parse<'a>(
+        input: impl Into<Span<'a>>,
+        mut cell: impl FnMut(char) -> Result<T>,
+    ) -> Result<Self> {
+        let input = input.into();
+        let mut cells = vec![];
+        let mut width = None;
+        let mut height = 0;
+
+        for line in input.lines() {
+            let len = line.as_str().chars().count();
+            match width {
+                None => width = Some(len),
+                Some(width) if width != len => {
+                    return Err(line
+                        .error(format!("the row has {} columns, expected {}", len, width))
+                        .with_help("all rows of the grid have the same length")
+                        .into())
+                }
+                Some(_) => (),
+            }
+
+            for (i, ch) in line.as_str().char_indices() {
+                let value = cell(ch).map_err(|err| {
+                    let error = line.slice(i..i + ch.len_utf8()).error(&err);
+                    match err.help() {
+                        Some(help) => error.with_help(help),
+                        None => error,
+                    }
+                })?;
+                cells.push(value);
+            }
+            height += 1;
+        }
+
+        match width {
+            None | Some(0) => Err(input.error("the grid is empty").into()),
+            Some(width) => Ok(Grid {
+                width,
+                height,
+                cells,
+            }),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, pos: Point) -> bool {
+        pos.x >= 0 && pos.y >= 0 && (pos.x as usize) < self.width && (pos.y as usize) < self.height
+    }
+
+    fn index_of(&self, pos: Point) -> Option<usize> {
+        self.contains(pos)
+            .then(|| pos.y as usize * self.width + pos.x as usize)
+    }
+
+    pub fn get(&self, pos: Point) -> Option<&T> {
+        self.index_of(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, pos: Point) -> Option<&mut T> {
+        self.index_of(pos).map(|i| &mut self.cells[i])
+    }
+
+    /// The neighbours above, right, below and left of the position that are inside the grid.
+    pub fn neighbours4(&self, pos: Point) -> impl Iterator<Item = Point> + '_ {
+        Direction::ALL
+            .into_iter()
+            .map(move |dir| pos + dir)
+            .filter(|&next| self.contains(next))
+    }
+
+    /// The neighbours including the diagonal ones that are inside the grid, clockwise from above.
+    pub fn neighbours8(&self, pos: Point) -> impl Iterator<Item = Point> + '_ {
+        NEIGHBOURS8
+            .into_iter()
+            .map(move |delta| pos + delta)
+            .filter(|&next| self.contains(next))
+    }
+
+    /// All positions row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| Point::new((i % width) as i32, (i / width) as i32))
+    }
+
+    /// All positions row by row with their cells.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// The first position row by row with a matching cell.
+    pub fn find(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Point> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(pos, _)| pos)
+    }
+
+    /// All positions with a matching cell.
+    pub fn find_all<'a>(
+        &'a self,
+        mut predicate: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = Point> + 'a {
+        self.iter()
+            .filter(move |(_, cell)| predicate(cell))
+            .map(|(pos, _)| pos)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Point) -> &T {
+        match self.index_of(pos) {
+            Some(i) => &self.cells[i],
+            None => panic!("position {} is outside the grid", pos),
+        }
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, pos: Point) -> &mut T {
+        match self.index_of(pos) {
+            Some(i) => &mut self.cells[i],
+            None => panic!("position {} is outside the grid", pos),
+        }
+    }
+}
+
+/// Prints the grid with one line per row.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Grid<char> {
+        Grid::parse("#.#\n.S.\n..E\n.##", |ch| ch).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = example();
+        assert_eq!((grid.width(), grid.height()), (3, 4));
+        assert_eq!(grid[Point::new(1, 1)], 'S');
+        assert_eq!(grid.to_string(), "#.#\n.S.\n..E\n.##");
+
+        assert!(Grid::parse("#.#\n.S\n", |ch| ch).is_err());
+        assert!(Grid::parse("", |ch| ch).is_err());
+        let error = Grid::try_parse("12\n3x", |ch| match ch.to_digit(10) {
+            Some(digit) => Ok(digit),
+            None => miette::bail!(help = "heights are 0 to 9", "not a digit"),
+        })
+        .unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 2: not a digit");
+        assert_eq!(error.help().unwrap().to_string(), "heights are 0 to 9");
+        assert_eq!(error.labels().unwrap().next().unwrap().offset(), 4);
+    }
+
+    #[test]
+    fn test_bounds() {
+        let mut grid = example();
+        assert!(grid.contains(Point::new(2, 3)));
+        assert!(!grid.contains(Point::new(3, 0)));
+        assert!(!grid.contains(Point::new(0, -1)));
+        assert_eq!(grid.get(Point::new(0, 4)), None);
+
+        *grid.get_mut(Point::new(1, 0)).unwrap() = 'O';
+        grid[Point::new(0, 3)] = 'O';
+        assert_eq!(grid.to_string(), "#O#\n.S.\n..E\nO##");
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = example();
+        let neighbours = grid.neighbours4(Point::new(0, 0)).collect::<Vec<_>>();
+        assert_eq!(neighbours, vec![Point::new(1, 0), Point::new(0, 1)]);
+        assert_eq!(grid.neighbours4(Point::new(1, 1)).count(), 4);
+        assert_eq!(grid.neighbours8(Point::new(1, 1)).count(), 8);
+        assert_eq!(grid.neighbours8(Point::new(2, 3)).count(), 3);
+    }
+
+    #[test]
+    fn test_find_and_views() {
+        let grid = example();
+        assert_eq!(grid.find(|&ch| ch == 'E'), Some(Point::new(2, 2)));
+        assert_eq!(grid.find(|&ch| ch == 'X'), None);
+        assert_eq!(grid.find_all(|&ch| ch == '#').count(), 4);
+        assert_eq!(grid.positions().nth(4), Some(Point::new(1, 1)));
+
+        assert_eq!(grid.rows().count(), 4);
+        assert_eq!(
+            grid.map(|&ch| ch == '#').find(|&wall| wall),
+            Some(Point::ZERO)
+        );
+    }
+}
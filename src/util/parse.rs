@@ -0,0 +1,389 @@
+use std::error::Error;
+use std::fmt;
+use std::iter;
+use std::ops::Range;
+use std::str::FromStr;
+
+use miette::{Diagnostic, LabeledSpan};
+
+/// An error in the puzzle input with the position of the bad spot, line and column are 1-based.
+/// As a diagnostic it labels the bad spot, so the report shows the offending line when the input
+/// is attached as source code (see `util::with_input`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+    pub help: Option<String>,
+    // the bad spot as byte range in the whole input
+    offset: usize,
+    len: usize,
+}
+
+impl ParseError {
+    pub fn with_help(mut self, help: impl fmt::Display) -> Self {
+        self.help = Some(help.to_string());
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+impl Diagnostic for ParseError {
+    fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        self.help
+            .as_ref()
+            .map(|help| Box::new(help) as Box<dyn fmt::Display>)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        let label = LabeledSpan::new_primary_with_span(None, (self.offset, self.len));
+        Some(Box::new(iter::once(label)))
+    }
+}
+
+pub type ParseResult<T> = Result<T, ParseError>;
+
+/// A piece of the puzzle input that knows where it starts, so errors can point at it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Span<'a> {
+    text: &'a str,
+    line: usize,
+    column: usize,
+    offset: usize,
+}
+
+/// The whole input as a span starting at line 1, column 1.
+pub fn input(text: &str) -> Span<'_> {
+    Span {
+        text,
+        line: 1,
+        column: 1,
+        offset: 0,
+    }
+}
+
+/// The lines of the input.
+pub fn lines(text: &str) -> impl Iterator<Item = Span<'_>> {
+    input(text).lines()
+}
+
+/// The blocks of the input that are separated by blank lines.
+pub fn sections(text: &str) -> Vec<Span<'_>> {
+    input(text).sections()
+}
+
+impl<'a> Span<'a> {
+    pub fn as_str(&self) -> &'a str {
+        self.text
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// An error pointing at the span.
+    pub fn error(&self, message: impl fmt::Display) -> ParseError {
+        ParseError {
+            line: self.line,
+            column: self.column,
+            message: message.to_string(),
+            help: None,
+            offset: self.offset,
+            len: self.text.len(),
+        }
+    }
+
+    // The span of `part`, which has to be a slice of this span's text.
+    fn sub(&self, part: &'a str) -> Span<'a> {
+        let start = (part.as_ptr() as usize)
+            .checked_sub(self.text.as_ptr() as usize)
+            .filter(|start| start + part.len() <= self.text.len())
+            .expect("part is not a slice of the span");
+
+        let before = &self.text[..start];
+        let (line, column) = match before.rfind('\n') {
+            Some(newline) => (
+                self.line + before.matches('\n').count(),
+                before[newline + 1..].chars().count() + 1,
+            ),
+            None => (self.line, self.column + before.chars().count()),
+        };
+
+        Span {
+            text: part,
+            line,
+            column,
+            offset: self.offset + start,
+        }
+    }
+
+    /// The part of the span at the byte `range`.
+    pub fn slice(&self, range: Range<usize>) -> Span<'a> {
+        self.sub(&self.text[range])
+    }
+
+    pub fn trim(&self) -> Span<'a> {
+        self.sub(self.text.trim())
+    }
+
+    /// The lines of the span without their line endings.
+    pub fn lines(self) -> impl Iterator<Item = Span<'a>> {
+        self.text.lines().map(move |line| self.sub(line))
+    }
+
+    /// The blocks of lines that are separated by one or more blank lines.
+    pub fn sections(&self) -> Vec<Span<'a>> {
+        let mut sections = vec![];
+        let mut current: Option<(Span<'a>, Span<'a>)> = None;
+
+        for line in self.lines() {
+            if line.text.trim().is_empty() {
+                sections.extend(
+                    current
+                        .take()
+                        .map(|(first, last)| self.between(first, last)),
+                );
+            } else {
+                current = Some(current.map_or((line, line), |(first, _)| (first, line)));
+            }
+        }
+        sections.extend(current.map(|(first, last)| self.between(first, last)));
+
+        sections
+    }
+
+    // The span from the start of `first` to the end of `last`.
+    fn between(&self, first: Span<'a>, last: Span<'a>) -> Span<'a> {
+        let start = first.offset - self.offset;
+        let end = last.offset - self.offset + last.text.len();
+        self.sub(&self.text[start..end])
+    }
+
+    /// Parses the trimmed span, e.g. a number.
+    pub fn parse<T>(&self) -> ParseResult<T>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let trimmed = self.trim();
+        trimmed
+            .text
+            .parse()
+            .map_err(|e| trimmed.error(format!("invalid value '{}': {}", trimmed.text, e)))
+    }
+
+    /// Splits the span at the first `separator`, both parts are trimmed.
+    pub fn split_once(&self, separator: &str) -> ParseResult<(Span<'a>, Span<'a>)> {
+        match self.text.split_once(separator) {
+            Some((first, second)) => Ok((self.sub(first).trim(), self.sub(second).trim())),
+            None => Err(self.error(format!("expected '{}' in '{}'", separator, self.text))),
+        }
+    }
+
+    /// Splits a `key: value` line into its trimmed key and value.
+    pub fn key_value(&self) -> ParseResult<(Span<'a>, Span<'a>)> {
+        let (key, value) = self.split_once(":")?;
+        if key.is_empty() {
+            return Err(key.error("expected a key before ':'"));
+        }
+        Ok((key, value))
+    }
+
+    /// The untrimmed parts of the span between the `separator`s.
+    pub fn split(self, separator: char) -> impl Iterator<Item = Span<'a>> {
+        self.text.split(separator).map(move |part| self.sub(part))
+    }
+
+    /// Parses every item of a list that is separated by `separator`, e.g. `1,2,3`.
+    pub fn list<T>(&self, separator: char) -> ParseResult<Vec<T>>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        if self.text.trim().is_empty() {
+            return Ok(vec![]);
+        }
+        self.split(separator)
+            .map(|item| {
+                if item.text.trim().is_empty() {
+                    Err(item.error("expected a value"))
+                } else {
+                    item.parse()
+                }
+            })
+            .collect()
+    }
+
+    /// Parses every item of a list that is separated by whitespace, e.g. `1 2 3`.
+    pub fn words<T>(&self) -> ParseResult<Vec<T>>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        self.tokens().map(|word| word.parse()).collect()
+    }
+
+    /// The parts of the span between whitespace.
+    pub fn tokens(self) -> impl Iterator<Item = Span<'a>> {
+        self.text.split_whitespace().map(move |word| self.sub(word))
+    }
+
+    /// Extracts all integers of the span and ignores everything around them, e.g.
+    /// `p=0,4 v=3,-3` contains 0, 4, 3 and -3. A `-` is a sign unless it follows a digit, so a
+    /// range like `1-3` contains 1 and 3.
+    pub fn ints<T>(&self) -> ParseResult<Vec<T>>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let bytes = self.text.as_bytes();
+        let mut ints = vec![];
+        let mut i = 0;
+
+        while i < bytes.len() {
+            let signed = bytes[i] == b'-'
+                && bytes.get(i + 1).is_some_and(u8::is_ascii_digit)
+                && (i == 0 || !bytes[i - 1].is_ascii_digit());
+            if !signed && !bytes[i].is_ascii_digit() {
+                i += 1;
+                continue;
+            }
+
+            let start = i;
+            i += 1;
+            while i < bytes.len() && bytes[i].is_ascii_digit() {
+                i += 1;
+            }
+            ints.push(self.sub(&self.text[start..i]).parse()?);
+        }
+
+        Ok(ints)
+    }
+
+    /// Extracts exactly `N` integers like [`Span::ints`].
+    pub fn ints_n<T, const N: usize>(&self) -> ParseResult<[T; N]>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let ints = self.ints()?;
+        let found = ints.len();
+        ints.try_into().map_err(|_| {
+            self.error(format!(
+                "expected {} numbers in '{}', found {}",
+                N, self.text, found
+            ))
+        })
+    }
+}
+
+impl<'a> From<&'a str> for Span<'a> {
+    fn from(text: &'a str) -> Self {
+        input(text)
+    }
+}
+
+impl fmt::Display for Span<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sections() {
+        let sections = sections("a\nb\n\n\nc\r\n  \r\nd\ne\n");
+        let texts = sections.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+        assert_eq!(texts, vec!["a\nb", "c", "d\ne"]);
+        let error = sections[2].lines().nth(1).unwrap().error("e");
+        assert_eq!(error.to_string(), "line 8, column 1: e");
+        assert!(super::sections("\n\n").is_empty());
+    }
+
+    #[test]
+    fn test_ints() {
+        let line = input("p=0,4 v=3,-3 w=1-3 x=+7");
+        assert_eq!(line.ints::<i32>().unwrap(), vec![0, 4, 3, -3, 1, 3, 7]);
+        assert_eq!(line.ints_n::<i64, 7>().unwrap()[3], -3);
+        assert_eq!(
+            line.ints_n::<i64, 2>().unwrap_err().message,
+            "expected 2 numbers in 'p=0,4 v=3,-3 w=1-3 x=+7', found 7"
+        );
+
+        let error = lines("Button A: X+94\nButton B: X+300")
+            .map(|line| line.ints::<u8>())
+            .collect::<ParseResult<Vec<_>>>()
+            .unwrap_err();
+        assert_eq!((error.line, error.column), (2, 13));
+    }
+
+    #[test]
+    fn test_lists() {
+        let line = input("r, wr, b");
+        assert_eq!(line.list::<String>(',').unwrap(), vec!["r", "wr", "b"]);
+        assert_eq!(input("3   4").words::<u32>().unwrap(), vec![3, 4]);
+        assert_eq!(input("").list::<u32>(',').unwrap(), Vec::<u32>::new());
+
+        let error = input("75,47,x1").list::<u32>(',').unwrap_err();
+        assert_eq!((error.line, error.column), (1, 7));
+        let error = input("75,,47").list::<u32>(',').unwrap_err();
+        assert_eq!((error.line, error.column), (1, 4));
+
+        let tokens = input("  bxl\t 1").tokens().collect::<Vec<_>>();
+        assert_eq!(tokens[1].as_str(), "1");
+        let error = tokens[1].error("operand");
+        assert_eq!((error.line, error.column), (1, 8));
+    }
+
+    #[test]
+    fn test_key_value() {
+        let mut lines = lines("Register A: 729\n\nProgram: 0,1,5,4");
+        let (key, value) = lines.next().unwrap().key_value().unwrap();
+        assert_eq!(key.as_str(), "Register A");
+        assert_eq!(value.parse::<u64>().unwrap(), 729);
+
+        let (key, value) = lines.nth(1).unwrap().key_value().unwrap();
+        assert_eq!(key.as_str(), "Program");
+        let error = value.error("program");
+        assert_eq!((error.line, error.column), (3, 10));
+        assert_eq!(value.list::<u8>(',').unwrap(), vec![0, 1, 5, 4]);
+
+        let error = input("Register A 729").key_value().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 1: expected ':' in 'Register A 729'"
+        );
+    }
+
+    #[test]
+    fn test_diagnostic() {
+        let error = lines("p=0,4 v=3,-3\np=6,3 v=2,x")
+            .nth(1)
+            .unwrap()
+            .slice(10..11)
+            .error("expected a number")
+            .with_help("velocities are integers");
+
+        let labels = error.labels().unwrap().collect::<Vec<_>>();
+        assert_eq!(labels.len(), 1);
+        assert_eq!((labels[0].offset(), labels[0].len()), (23, 1));
+        assert_eq!(
+            error.help().map(|help| help.to_string()).as_deref(),
+            Some("velocities are integers")
+        );
+    }
+}
@@ -0,0 +1,133 @@
+use std::fmt;
+use std::ops::{Add, AddAssign};
+
+use glam::IVec2;
+
+/// A position in a grid, `x` is the column and `y` the row. Rows grow downwards, so `Up` is
+/// negative `y`.
+pub type Point = IVec2;
+
+pub fn manhattan(a: Point, b: Point) -> u32 {
+    a.x.abs_diff(b.x) + a.y.abs_diff(b.y)
+}
+
+/// One of the four directions of a grid, in clockwise order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub fn delta(self) -> Point {
+        match self {
+            Direction::Up => Point::NEG_Y,
+            Direction::Right => Point::X,
+            Direction::Down => Point::Y,
+            Direction::Left => Point::NEG_X,
+        }
+    }
+
+    /// Turns clockwise by 90 degrees.
+    pub fn turn_right(self) -> Self {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    /// Turns counterclockwise by 90 degrees.
+    pub fn turn_left(self) -> Self {
+        self.turn_right().reverse()
+    }
+
+    pub fn reverse(self) -> Self {
+        self.turn_right().turn_right()
+    }
+
+    /// Parses the arrows `^`, `>`, `v` and `<` of the puzzle descriptions.
+    pub fn from_arrow(arrow: char) -> Option<Self> {
+        match arrow {
+            '^' => Some(Direction::Up),
+            '>' => Some(Direction::Right),
+            'v' => Some(Direction::Down),
+            '<' => Some(Direction::Left),
+            _ => None,
+        }
+    }
+
+    pub fn arrow(self) -> char {
+        match self {
+            Direction::Up => '^',
+            Direction::Right => '>',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+        }
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.arrow())
+    }
+}
+
+impl Add<Direction> for Point {
+    type Output = Point;
+
+    fn add(self, dir: Direction) -> Point {
+        self + dir.delta()
+    }
+}
+
+impl AddAssign<Direction> for Point {
+    fn add_assign(&mut self, dir: Direction) {
+        *self += dir.delta();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turns() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.reverse(), Direction::Right);
+        for dir in Direction::ALL {
+            assert_eq!(dir.turn_right().turn_left(), dir);
+            // turning right is IVec2::perp in a grid with rows growing downwards
+            assert_eq!(dir.turn_right().delta(), dir.delta().perp());
+        }
+    }
+
+    #[test]
+    fn test_arrows() {
+        assert_eq!(Direction::from_arrow('v'), Some(Direction::Down));
+        assert_eq!(Direction::from_arrow('x'), None);
+        for dir in Direction::ALL {
+            assert_eq!(Direction::from_arrow(dir.arrow()), Some(dir));
+        }
+    }
+
+    #[test]
+    fn test_points() {
+        let mut pos = Point::new(3, 4);
+        assert_eq!(pos + Direction::Up, Point::new(3, 3));
+        pos += Direction::Left;
+        assert_eq!(pos, Point::new(2, 4));
+        assert_eq!(manhattan(Point::ZERO, Point::new(-2, 5)), 7);
+    }
+}
@@ -0,0 +1,204 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use miette::{miette, Result};
+use serde_json::json;
+
+use super::render::{Canvas, Render};
+
+// clears the screen and moves the cursor to the top left before every frame
+const CLEAR: &str = "\x1b[H\x1b[2J";
+
+// the frames are recorded by the solvers, the CLI starts and finishes the recording
+static RECORDING: AtomicBool = AtomicBool::new(false);
+static RECORDER: Mutex<Option<Recorder>> = Mutex::new(None);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Options {
+    /// frames per second of the replay
+    pub fps: u32,
+    /// only every n-th frame is written, 1 writes all
+    pub skip: u32,
+}
+
+/// Writes frames as an asciinema v2 cast: a JSON header with the terminal size, then one
+/// `[time, "o", data]` event per frame.
+pub struct Cast<W: Write> {
+    out: W,
+    options: Options,
+    // all frames the simulation produced and the ones that were written
+    seen: u64,
+    written: u64,
+}
+
+impl<W: Write> Cast<W> {
+    pub fn new(out: W, options: Options) -> Self {
+        Cast {
+            out,
+            options: Options {
+                fps: options.fps.max(1),
+                skip: options.skip.max(1),
+            },
+            seen: 0,
+            written: 0,
+        }
+    }
+
+    /// Adds a frame, the header is written with the size of the first frame.
+    pub fn frame(&mut self, canvas: &Canvas) -> io::Result<()> {
+        self.seen += 1;
+        if !(self.seen - 1).is_multiple_of(self.options.skip as u64) {
+            return Ok(());
+        }
+
+        let screen = canvas.render(true);
+        if self.written == 0 {
+            let width = screen.lines().map(visible_width).max().unwrap_or(0);
+            let height = screen.lines().count();
+            let header = json!({ "version": 2, "width": width, "height": height });
+            writeln!(self.out, "{}", header)?;
+        }
+
+        let time = self.written as f64 / self.options.fps as f64;
+        let data = format!("{}{}", CLEAR, screen.replace('\n', "\r\n"));
+        writeln!(self.out, "{}", json!([time, "o", data]))?;
+        self.written += 1;
+        Ok(())
+    }
+
+    pub fn written(&self) -> u64 {
+        self.written
+    }
+
+    pub fn finish(mut self) -> io::Result<W> {
+        self.out.flush()?;
+        Ok(self.out)
+    }
+}
+
+// the width of a line without its ANSI escape sequences
+fn visible_width(line: &str) -> usize {
+    let mut width = 0;
+    let mut in_escape = false;
+    for ch in line.chars() {
+        match ch {
+            '\x1b' => in_escape = true,
+            'm' if in_escape => in_escape = false,
+            _ if in_escape => (),
+            _ => width += 1,
+        }
+    }
+    width
+}
+
+struct Recorder {
+    path: PathBuf,
+    cast: Cast<BufWriter<File>>,
+    // the first write error, reported when the recording is finished
+    error: Option<io::Error>,
+}
+
+/// Starts recording the frames of the solvers to the cast file at `path`.
+pub fn start(path: &Path, options: Options) -> Result<()> {
+    let file = File::create(path)
+        .map_err(|e| miette!("Failed to create cast file {}: {}", path.display(), e))?;
+    *RECORDER.lock().unwrap() = Some(Recorder {
+        path: path.to_path_buf(),
+        cast: Cast::new(BufWriter::new(file), options),
+        error: None,
+    });
+    RECORDING.store(true, Ordering::Relaxed);
+    Ok(())
+}
+
+pub fn is_recording() -> bool {
+    RECORDING.load(Ordering::Relaxed)
+}
+
+/// Records a step of a simulation if a recording was started. The canvas is only drawn then,
+/// so solvers can call this on every step.
+pub fn frame(state: &impl Render) {
+    if !is_recording() {
+        return;
+    }
+    if let Some(recorder) = RECORDER.lock().unwrap().as_mut() {
+        if recorder.error.is_none() {
+            recorder.error = recorder.cast.frame(&state.canvas()).err();
+        }
+    }
+}
+
+/// Stops the recording and returns the path of the cast file with the number of frames.
+pub fn finish() -> Result<Option<(PathBuf, u64)>> {
+    RECORDING.store(false, Ordering::Relaxed);
+    let Some(recorder) = RECORDER.lock().unwrap().take() else {
+        return Ok(None);
+    };
+
+    let written = recorder.cast.written();
+    let result = match recorder.error {
+        Some(e) => Err(e),
+        None => recorder.cast.finish().map(|_| ()),
+    };
+    result.map_err(|e| {
+        miette!(
+            "Failed to write cast file {}: {}",
+            recorder.path.display(),
+            e
+        )
+    })?;
+
+    Ok(Some((recorder.path, written)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::Grid;
+
+    fn canvas(input: &str) -> Canvas {
+        Grid::parse(input, |ch| ch).unwrap().canvas()
+    }
+
+    fn events(cast: Cast<Vec<u8>>) -> Vec<serde_json::Value> {
+        let out = String::from_utf8(cast.finish().unwrap()).unwrap();
+        out.lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_cast() {
+        let mut cast = Cast::new(vec![], Options { fps: 4, skip: 1 });
+        cast.frame(&canvas("#..\n.@.")).unwrap();
+        cast.frame(&canvas("#..\n..@")).unwrap();
+
+        let events = events(cast);
+        assert_eq!(events[0], json!({ "version": 2, "width": 3, "height": 2 }));
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[2][0], 0.25);
+        assert_eq!(events[2][1], "o");
+        let data = events[2][2].as_str().unwrap();
+        assert!(data.starts_with(CLEAR));
+        assert_eq!(data.matches("\r\n").count(), 1);
+    }
+
+    #[test]
+    fn test_skip_frames() {
+        let mut cast = Cast::new(vec![], Options { fps: 10, skip: 3 });
+        for _ in 0..7 {
+            cast.frame(&canvas("#.")).unwrap();
+        }
+        assert_eq!(cast.written(), 3);
+        // header and the frames 1, 4 and 7
+        assert_eq!(events(cast).len(), 4);
+    }
+
+    #[test]
+    fn test_visible_width() {
+        assert_eq!(visible_width("\x1b[31m#\x1b[0m.\x1b[1;93m@\x1b[0m"), 3);
+    }
+}
@@ -0,0 +1,278 @@
+use std::env;
+use std::fmt;
+use std::io::{self, IsTerminal};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use owo_colors::OwoColorize;
+
+use super::grid::Grid;
+use super::point::{Direction, Point};
+
+// set once by the CLI before the days are solved
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static COLOUR: AtomicBool = AtomicBool::new(false);
+
+/// A grid state of a puzzle that can be shown in the terminal.
+pub trait Render {
+    fn canvas(&self) -> Canvas;
+}
+
+/// Turns rendering on, `colour` adds ANSI colours to the output.
+pub fn enable(colour: bool) {
+    ENABLED.store(true, Ordering::Relaxed);
+    COLOUR.store(colour, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Colours only make sense on a terminal, `NO_COLOR` turns them off as well.
+pub fn colour_supported() -> bool {
+    io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+}
+
+/// Prints the state with a title on stdout if rendering is enabled. The canvas is only drawn
+/// then, so solvers can call this on hot paths.
+pub fn show(title: impl fmt::Display, state: &impl Render) {
+    if !is_enabled() {
+        return;
+    }
+    println!("{}", title);
+    println!("{}", state.canvas().render(COLOUR.load(Ordering::Relaxed)));
+}
+
+/// What a glyph stands for, each kind has its own colour.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Style {
+    Plain,
+    Wall,
+    Floor,
+    Start,
+    End,
+    /// the robot, guard or reindeer that moves around
+    Actor,
+    /// boxes, antennas and other things on the floor
+    Object,
+    Path,
+    Visited,
+    Highlight,
+}
+
+impl Style {
+    /// The style of a glyph of the puzzle maps, so the same glyph looks the same in every day.
+    pub fn of(glyph: char) -> Self {
+        match glyph {
+            '#' => Style::Wall,
+            '.' => Style::Floor,
+            'S' => Style::Start,
+            'E' => Style::End,
+            '@' | '^' | '>' | 'v' | '<' => Style::Actor,
+            VISITED => Style::Visited,
+            ch if ch.is_ascii_alphanumeric() || "O[]".contains(ch) => Style::Object,
+            _ => Style::Plain,
+        }
+    }
+
+    fn paint(self, glyph: char) -> String {
+        match self {
+            Style::Plain => glyph.to_string(),
+            Style::Wall => glyph.red().to_string(),
+            Style::Floor => glyph.dimmed().to_string(),
+            Style::Start => glyph.yellow().to_string(),
+            Style::End => glyph.green().to_string(),
+            Style::Actor => glyph.bright_yellow().bold().to_string(),
+            Style::Object => glyph.blue().to_string(),
+            Style::Path => glyph.green().to_string(),
+            Style::Visited => glyph.cyan().to_string(),
+            Style::Highlight => glyph.black().on_yellow().to_string(),
+        }
+    }
+
+    /// The colour of the style in exported images.
+    pub fn rgb(self) -> [u8; 3] {
+        match self {
+            Style::Plain => [255, 255, 255],
+            Style::Wall => [150, 40, 40],
+            Style::Floor => [30, 30, 30],
+            Style::Start => [230, 200, 40],
+            Style::End => [60, 190, 60],
+            Style::Actor => [255, 240, 120],
+            Style::Object => [70, 110, 220],
+            Style::Path => [90, 230, 90],
+            Style::Visited => [60, 180, 190],
+            Style::Highlight => [255, 160, 0],
+        }
+    }
+}
+
+/// The glyph of visited floor tiles.
+pub const VISITED: char = 'X';
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cell {
+    pub glyph: char,
+    pub style: Style,
+}
+
+impl Cell {
+    pub fn new(glyph: char) -> Self {
+        Cell {
+            glyph,
+            style: Style::of(glyph),
+        }
+    }
+}
+
+/// A grid of styled glyphs with overlays on top of the puzzle map.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Canvas {
+    cells: Grid<Cell>,
+}
+
+impl Canvas {
+    /// Draws every cell of the grid with the glyph it maps to.
+    pub fn new<T>(grid: &Grid<T>, mut glyph: impl FnMut(&T) -> char) -> Self {
+        Canvas {
+            cells: grid.map(|cell| Cell::new(glyph(cell))),
+        }
+    }
+
+    pub fn from_chars(grid: &Grid<char>) -> Self {
+        Canvas::new(grid, |&ch| ch)
+    }
+
+    pub fn width(&self) -> usize {
+        self.cells.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.height()
+    }
+
+    /// All positions row by row with their cells.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &Cell)> {
+        self.cells.iter()
+    }
+
+    /// Puts a glyph with its own style at the position, positions outside are ignored.
+    pub fn set(&mut self, pos: Point, glyph: char, style: Style) {
+        if let Some(cell) = self.cells.get_mut(pos) {
+            *cell = Cell { glyph, style };
+        }
+    }
+
+    /// Marks the floor tiles at the positions as visited, other tiles only change their colour.
+    pub fn visited(mut self, positions: impl IntoIterator<Item = Point>) -> Self {
+        for pos in positions {
+            if let Some(cell) = self.cells.get_mut(pos) {
+                if cell.glyph == '.' {
+                    cell.glyph = VISITED;
+                }
+                cell.style = Style::Visited;
+            }
+        }
+        self
+    }
+
+    /// Draws the steps of a path as arrows, the start and end tiles of the map are kept.
+    pub fn path(mut self, steps: impl IntoIterator<Item = (Point, Direction)>) -> Self {
+        for (pos, dir) in steps {
+            if let Some(cell) = self.cells.get_mut(pos) {
+                if !matches!(cell.style, Style::Start | Style::End) {
+                    *cell = Cell {
+                        glyph: dir.arrow(),
+                        style: Style::Path,
+                    };
+                }
+            }
+        }
+        self
+    }
+
+    /// Highlights the positions and keeps their glyphs.
+    pub fn highlight(mut self, positions: impl IntoIterator<Item = Point>) -> Self {
+        for pos in positions {
+            if let Some(cell) = self.cells.get_mut(pos) {
+                cell.style = Style::Highlight;
+            }
+        }
+        self
+    }
+
+    /// The canvas with one line per row, with or without ANSI colours.
+    pub fn render(&self, colour: bool) -> String {
+        self.cells
+            .rows()
+            .map(|row| {
+                row.iter()
+                    .map(|cell| match colour {
+                        true => cell.style.paint(cell.glyph),
+                        false => cell.glyph.to_string(),
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// The canvas without colours.
+impl fmt::Display for Canvas {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.render(false))
+    }
+}
+
+impl Render for Grid<char> {
+    fn canvas(&self) -> Canvas {
+        Canvas::from_chars(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn maze() -> Grid<char> {
+        Grid::parse("#####\n#S..#\n#.#E#\n#####", |ch| ch).unwrap()
+    }
+
+    #[test]
+    fn test_styles() {
+        assert_eq!(Style::of('#'), Style::Wall);
+        assert_eq!(Style::of('@'), Style::Actor);
+        assert_eq!(Style::of('['), Style::Object);
+        assert_eq!(Style::of('A'), Style::Object);
+        assert_eq!(Style::of(' '), Style::Plain);
+    }
+
+    #[test]
+    fn test_overlays() {
+        let canvas = maze()
+            .canvas()
+            .visited([Point::new(1, 2), Point::new(0, 0)])
+            .path([
+                (Point::new(1, 1), Direction::Right),
+                (Point::new(2, 1), Direction::Right),
+                (Point::new(3, 1), Direction::Down),
+            ])
+            .highlight([Point::new(3, 2), Point::new(9, 9)]);
+
+        assert_eq!(canvas.to_string(), "#####\n#S>v#\n#X#E#\n#####");
+        assert_eq!(canvas.cells.get(Point::ZERO).unwrap().style, Style::Visited);
+        assert_eq!(
+            canvas.cells.get(Point::new(3, 2)).unwrap().style,
+            Style::Highlight
+        );
+    }
+
+    #[test]
+    fn test_colours() {
+        let canvas = maze().canvas();
+        assert_eq!(canvas.render(false), canvas.to_string());
+        let coloured = canvas.render(true);
+        assert!(coloured.contains('\x1b'));
+        assert_eq!(coloured.lines().count(), 4);
+    }
+}
@@ -0,0 +1,151 @@
+use std::any::Any;
+
+use miette::{miette, Result};
+
+use super::{Answer, Params, Part, Solver};
+
+/// A puzzle solution with a parse phase that runs once per input and two parts that share the
+/// parsed input. A part can store its results in the parsed input, so the other part can reuse
+/// them instead of computing them again.
+pub trait Solution {
+    type Parsed: 'static;
+
+    /// Whether the parse phase does any work of its own, it is only reported then.
+    const PARSES: bool = true;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed>;
+
+    fn part1(&self, parsed: &mut Self::Parsed, params: &Params) -> Result<Answer>;
+
+    fn part2(&self, parsed: &mut Self::Parsed, params: &Params) -> Result<Answer>;
+}
+
+/// Adapts the two solver functions of a day to a [`Solution`], both parse the input themselves.
+pub struct FnSolution {
+    pub part1: Solver,
+    pub part2: Solver,
+}
+
+impl Solution for FnSolution {
+    type Parsed = String;
+
+    const PARSES: bool = false;
+
+    fn parse(&self, input: &str) -> Result<String> {
+        Ok(input.to_string())
+    }
+
+    fn part1(&self, input: &mut String, params: &Params) -> Result<Answer> {
+        (self.part1)(input, params)
+    }
+
+    fn part2(&self, input: &mut String, params: &Params) -> Result<Answer> {
+        (self.part2)(input, params)
+    }
+}
+
+/// A [`Solution`] with the parsed type erased, so days with different types fit into one list.
+pub(crate) trait DynSolution {
+    fn parses(&self) -> bool;
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>>;
+
+    fn solve(&self, part: Part, parsed: &mut dyn Any, params: &Params) -> Result<Answer>;
+}
+
+impl<S: Solution> DynSolution for S {
+    fn parses(&self) -> bool {
+        S::PARSES
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>> {
+        Ok(Box::new(Solution::parse(self, input)?))
+    }
+
+    fn solve(&self, part: Part, parsed: &mut dyn Any, params: &Params) -> Result<Answer> {
+        let parsed = parsed
+            .downcast_mut::<S::Parsed>()
+            .ok_or_else(|| miette!("The parsed input is of another solution."))?;
+        match part {
+            Part::One => self.part1(parsed, params),
+            Part::Two => self.part2(parsed, params),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // counts the numbers in part 1 and reuses the numbers and their count in part 2
+    struct Numbers;
+
+    struct Parsed {
+        numbers: Vec<i64>,
+        count: Option<usize>,
+    }
+
+    impl Solution for Numbers {
+        type Parsed = Parsed;
+
+        fn parse(&self, input: &str) -> Result<Parsed> {
+            let numbers = input
+                .split(' ')
+                .map(|n| n.parse().map_err(|_| miette!("not a number: {}", n)))
+                .collect::<Result<_>>()?;
+            Ok(Parsed {
+                numbers,
+                count: None,
+            })
+        }
+
+        fn part1(&self, parsed: &mut Parsed, _params: &Params) -> Result<Answer> {
+            parsed.count = Some(parsed.numbers.len());
+            Ok(parsed.numbers.len().into())
+        }
+
+        fn part2(&self, parsed: &mut Parsed, _params: &Params) -> Result<Answer> {
+            let sum = parsed.numbers.iter().sum::<i64>();
+            Ok(match parsed.count {
+                Some(count) => format!("{}/{}", sum, count).into(),
+                None => sum.into(),
+            })
+        }
+    }
+
+    #[test]
+    fn test_shared_parsed_input() {
+        let solution: &dyn DynSolution = &Numbers;
+        assert!(solution.parses());
+        let mut parsed = solution.parse("1 2 3").unwrap();
+        let params = Params::default();
+        assert_eq!(
+            solution.solve(Part::Two, &mut *parsed, &params).unwrap(),
+            "6"
+        );
+        assert_eq!(
+            solution.solve(Part::One, &mut *parsed, &params).unwrap(),
+            "3"
+        );
+        assert_eq!(
+            solution.solve(Part::Two, &mut *parsed, &params).unwrap(),
+            "6/3"
+        );
+        assert!(solution.parse("1 x").is_err());
+    }
+
+    #[test]
+    fn test_fn_solution() {
+        let solution: &dyn DynSolution = &FnSolution {
+            part1: |input, _| Ok(input.len().into()),
+            part2: |_, _| Ok(Answer::Unsolved),
+        };
+        assert!(!solution.parses());
+        let mut parsed = solution.parse("abc").unwrap();
+        let answer = solution.solve(Part::One, &mut *parsed, &Params::default());
+        assert_eq!(answer.unwrap(), "3");
+        assert!(solution
+            .solve(Part::One, &mut 42, &Params::default())
+            .is_err());
+    }
+}